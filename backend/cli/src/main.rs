@@ -0,0 +1,893 @@
+use anyhow::{Result, Context};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use debeon_core::api::{http_client, RobloxApiClient};
+use debeon_core::config::{ConfigManager, PresetLibrary};
+use debeon_core::error::DebeonError;
+use debeon_core::audit::{self, AuditFilter};
+use debeon_core::models::{
+    ApiResponse, AuditSource, CombineRequest, ConfigCoverage, ConflictPolicy, FlagDiff,
+    OwnedRemoval, PresetRequest, ReapplyReason, ReapplyReport, UserSettingsWrite,
+};
+use debeon_core::roblox::{
+    FlagCatalog, FlagImporter, FlagSelector, InstallTarget, RobloxManager, UpstreamFlags,
+};
+use debeon_core::settings::DebeonSettings;
+
+/// Headless Debeon: manage Roblox installations, profiles, fast flags and
+/// backups without the frontend or the HTTP server.
+#[derive(Parser)]
+#[command(name = "debeon", version)]
+struct Cli {
+    /// Print results as the same JSON envelope the HTTP API returns
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Load a different settings.toml
+    #[arg(long, global = true, value_name = "FILE")]
+    settings: Option<PathBuf>,
+
+    /// Override a setting, e.g. --set api.users_url=http://127.0.0.1:9000
+    #[arg(long = "set", global = true, value_name = "KEY=VALUE")]
+    overrides: Vec<String>,
+
+    /// Installation to act on, as listed by `debeon installations`, or "all";
+    /// defaults to the primary installation
+    #[arg(long, global = true, value_name = "ID")]
+    installation: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List detected Roblox installations
+    Installations,
+    /// Show the properties of Roblox's user settings XML
+    UserSettings,
+    /// Apply a saved profile to an installation
+    Apply {
+        profile: String,
+        /// Print the changes that would be made without writing them
+        #[arg(long)]
+        dry_run: bool,
+        /// Replace the previous profile: remove keys Debeon wrote that this
+        /// one does not set, keeping hand-edited ones
+        #[arg(long)]
+        switch: bool,
+    },
+    /// Read and edit fast flags in ClientAppSettings.json
+    #[command(subcommand)]
+    Flags(FlagsCommand),
+    /// Manage configuration profiles
+    #[command(subcommand)]
+    Profiles(ProfilesCommand),
+    /// List and manage built-in and user flag presets
+    #[command(subcommand)]
+    Presets(PresetsCommand),
+    /// Manage ClientAppSettings.json backups
+    #[command(subcommand)]
+    Backups(BackupsCommand),
+    /// Show the log of ClientAppSettings.json writes, newest first
+    Audit {
+        /// Only entries at or after this time (RFC 3339 or YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries at or before this time (RFC 3339), or on or before this day (YYYY-MM-DD)
+        #[arg(long)]
+        until: Option<String>,
+        /// Only entries that changed this flag
+        #[arg(long)]
+        key: Option<String>,
+        /// Show at most this many entries
+        #[arg(long)]
+        limit: Option<usize>,
+    },
+    /// Show the profile and flags remembered per channel and what the server
+    /// watcher re-applied after Roblox updates
+    Reapply {
+        /// Write the remembered settings back into the installation now
+        #[arg(long)]
+        now: bool,
+    },
+    /// Look up a Roblox user by ID
+    User { id: u64 },
+    /// Search Roblox users by keyword
+    SearchUsers { keyword: String },
+    /// Look up asset details by ID
+    Asset { id: u64 },
+    /// Look up a game by universe ID
+    Game { universe_id: u64 },
+    /// Show the latest published Roblox client version
+    ClientVersion,
+}
+
+#[derive(Subcommand)]
+enum FlagsCommand {
+    /// Print all flags, or only the given keys
+    Get { keys: Vec<String> },
+    /// Set flags given as KEY=VALUE pairs
+    Set {
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        flags: Vec<String>,
+        /// Print the changes that would be made without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Remove flags by key, by pattern, or all of them (a backup is taken first)
+    Remove(RemoveTarget),
+    /// Import flags from Bloxstrap, Fishstrap, Sober or KEY=VALUE text
+    Import {
+        /// File to read, or - for stdin
+        file: PathBuf,
+        /// Merge into this profile's custom flags instead of the live file
+        #[arg(long)]
+        profile: Option<String>,
+        /// Only report what would be imported
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Search the catalog of known flags
+    Catalog { search: Option<String> },
+    /// Diff the live flags against a backup or a JSON file
+    Diff(DiffTarget),
+    /// Show which keys Debeon wrote, which were changed by hand since, and
+    /// which it never wrote
+    Ownership,
+    /// Remove every key Debeon wrote, keeping hand-edited and other keys (a
+    /// backup is taken first)
+    Uninstall,
+    /// Compare the live flags with the client settings Roblox publishes
+    Remote {
+        /// Client settings application; defaults to PCStudioApp for Studio
+        /// and PCDesktopClient otherwise
+        #[arg(long)]
+        application: Option<String>,
+        /// Deployment channel
+        #[arg(long, default_value = "live")]
+        channel: String,
+        /// Download the published settings even if the cached copy is fresh
+        #[arg(long)]
+        refresh: bool,
+    },
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct RemoveTarget {
+    /// Flag names to remove
+    keys: Vec<String>,
+    /// Remove every flag whose name matches this regular expression
+    #[arg(long)]
+    pattern: Option<String>,
+    /// Remove every flag
+    #[arg(long)]
+    all: bool,
+}
+
+#[derive(Args)]
+#[group(required = true, multiple = false)]
+struct DiffTarget {
+    /// Backup name, as printed by `debeon backups list`
+    #[arg(long)]
+    backup: Option<String>,
+    /// Path to a ClientAppSettings-style JSON file
+    #[arg(long)]
+    file: Option<PathBuf>,
+}
+
+#[derive(Subcommand)]
+enum ProfilesCommand {
+    List,
+    Show { name: String },
+    Delete { name: String },
+    Rename { name: String, new_name: String },
+    Duplicate { name: String, new_name: String },
+    Import {
+        file: PathBuf,
+        name: String,
+        #[arg(long)]
+        overwrite: bool,
+    },
+    Export { name: String, file: PathBuf },
+}
+
+#[derive(Subcommand)]
+enum PresetsCommand {
+    List,
+    Show { name: String },
+    /// Create or replace a user preset
+    Save {
+        name: String,
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        flags: Vec<String>,
+        #[arg(long, default_value = "")]
+        category: String,
+        #[arg(long, default_value = "")]
+        description: String,
+    },
+    Delete { name: String },
+    /// Combine presets, a profile and custom flags, reporting conflicting keys
+    Combine {
+        presets: Vec<String>,
+        /// Include the flags a saved profile maps to, and its custom flags
+        #[arg(long)]
+        profile: Option<String>,
+        /// Extra custom flags
+        #[arg(long = "flag", value_name = "KEY=VALUE")]
+        flags: Vec<String>,
+        #[arg(long, value_enum, default_value = "error")]
+        policy: PolicyArg,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum PolicyArg {
+    Error,
+    LastWins,
+    PreferPreset,
+    PreferCustom,
+}
+
+impl From<PolicyArg> for ConflictPolicy {
+    fn from(policy: PolicyArg) -> Self {
+        match policy {
+            PolicyArg::Error => ConflictPolicy::Error,
+            PolicyArg::LastWins => ConflictPolicy::LastWins,
+            PolicyArg::PreferPreset => ConflictPolicy::PreferPreset,
+            PolicyArg::PreferCustom => ConflictPolicy::PreferCustom,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum BackupsCommand {
+    List,
+    Create,
+    Restore { name: String },
+    Diff { name: String },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    let json = cli.json;
+
+    if let Err(e) = run(cli).await {
+        let error = DebeonError::from(e);
+
+        if json {
+            print_json(&ApiResponse::<()>::error(&error));
+        } else {
+            eprintln!("error: {}", error);
+        }
+
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let mut settings_args = Vec::new();
+
+    if let Some(path) = &cli.settings {
+        settings_args.push("--settings".to_string());
+        settings_args.push(path.to_string_lossy().to_string());
+    }
+
+    for assignment in &cli.overrides {
+        settings_args.push("--set".to_string());
+        settings_args.push(assignment.clone());
+    }
+
+    let settings = DebeonSettings::load_from(std::env::vars(), &settings_args)?;
+    let json = cli.json;
+    let target = InstallTarget::from_param(cli.installation.as_deref());
+
+    match cli.command {
+        Command::Installations => {
+            let manager = RobloxManager::new(&settings, http_client()?)?;
+            let installations = manager.find_installations()?;
+            output(json, &installations, |installations| {
+                for i in installations {
+                    let marker = if i.primary { "*" } else { " " };
+                    println!(
+                        "{}{}\t{}\t{}\t{}\t{}",
+                        marker, i.id, i.channel, i.version, i.last_modified, i.path
+                    );
+                }
+            });
+        }
+        Command::UserSettings => {
+            let manager = RobloxManager::new(&settings, http_client()?)?;
+            let user_settings = manager.user_settings(&target)?;
+            output(json, &user_settings, |user_settings| {
+                for (name, setting) in &user_settings.properties {
+                    println!("{}\t{}\t{}", name, setting.kind, setting.value);
+                }
+            });
+        }
+        Command::Apply { profile, dry_run, switch } => {
+            let config = ConfigManager::new()?.load_config(&profile)?;
+            let manager = RobloxManager::new(&settings, http_client()?)?;
+            if dry_run && switch {
+                output(json, &manager.preview_switch(&target, &config)?, print_diff);
+            } else if dry_run {
+                output(json, &manager.preview_config(&target, &config)?, print_diff);
+            } else if switch {
+                let source = AuditSource::Profile { name: profile.clone() };
+                let removal = manager.switch_config(&target, &config, source)?;
+                output(json, &removal, |removal| {
+                    print_owned_removal(removal);
+                    print_user_settings_writes(&removal.user_settings);
+                    if let Some(coverage) = &removal.coverage {
+                        print_coverage(coverage);
+                    }
+                    println!("Switched to profile {} on {}", profile, removal.installations.join(", "));
+                });
+            } else {
+                let source = AuditSource::Profile { name: profile.clone() };
+                let applied = manager.apply_config(&target, &config, source)?;
+                output(json, &applied, |applied| {
+                    print_user_settings_writes(&applied.user_settings);
+                    print_coverage(&applied.coverage);
+                    println!("Applied profile {} to {}", profile, applied.installations.join(", "));
+                });
+            }
+        }
+        Command::Flags(command) => run_flags(command, &settings, &target, json).await?,
+        Command::Profiles(command) => run_profiles(command, json)?,
+        Command::Presets(command) => run_presets(command, json)?,
+        Command::Backups(command) => {
+            run_backups(command, &settings, cli.installation.as_deref(), json)?
+        }
+        Command::Audit { since, until, key, limit } => {
+            let filter = AuditFilter {
+                since: since.as_deref().map(audit::parse_time).transpose()?,
+                until: until.as_deref().map(audit::parse_until).transpose()?,
+                key,
+                limit,
+            };
+            let manager = RobloxManager::new(&settings, http_client()?)?;
+            let entries = manager.audit_log().query(&filter)?;
+            output(json, &entries, |entries| {
+                for entry in entries {
+                    println!(
+                        "{}\t{}\t{}\t{}",
+                        entry.timestamp,
+                        entry.operation.as_str(),
+                        entry.source,
+                        entry.installation
+                    );
+                    for (key, change) in &entry.changes {
+                        let show = |value: &Option<Value>| {
+                            value.as_ref().map(Value::to_string).unwrap_or_else(|| "-".to_string())
+                        };
+                        println!("  {}: {} -> {}", key, show(&change.before), show(&change.after));
+                    }
+                }
+            });
+        }
+        Command::Reapply { now } => {
+            let manager = RobloxManager::new(&settings, http_client()?)?;
+            if now {
+                let mut reports = Vec::new();
+                for installation in manager.resolve_installations(&target)? {
+                    reports.extend(manager.reapply(&installation, ReapplyReason::Manual)?);
+                }
+                output(json, &reports, |reports| {
+                    if reports.is_empty() {
+                        println!("Nothing to re-apply");
+                    }
+                    reports.iter().for_each(print_reapply_report);
+                });
+            } else {
+                let mut status = manager.reapply_status()?;
+                status.enabled = settings.watcher.reapply;
+                output(json, &status, |status| {
+                    let enabled = if status.enabled { "on" } else { "off" };
+                    println!("watcher re-apply: {}", enabled);
+                    for (channel, state) in &status.channels {
+                        println!(
+                            "{}	profile: {}	{} flag(s)	{}",
+                            channel,
+                            state.profile.as_deref().unwrap_or("-"),
+                            state.flags.len(),
+                            state.updated
+                        );
+                    }
+                    status.history.iter().for_each(print_reapply_report);
+                });
+            }
+        }
+        Command::User { id } => {
+            let user = api_client(&settings)?.get_user_info(id).await?;
+            output(json, &user, |u| {
+                println!("{} (@{}) #{}", u.display_name, u.name, u.id);
+                println!("created: {}", u.created);
+                if u.is_banned {
+                    println!("banned");
+                }
+            });
+        }
+        Command::SearchUsers { keyword } => {
+            let users = api_client(&settings)?.search_users(&keyword).await?;
+            output(json, &users, |users| {
+                for u in users {
+                    println!("{}\t{}\t{}", u.id, u.name, u.display_name);
+                }
+            });
+        }
+        Command::Asset { id } => {
+            let asset = api_client(&settings)?.get_asset_details(id).await?;
+            output(json, &asset, |a| {
+                println!("{} #{}", a.name, a.id);
+                println!("creator: {} ({})", a.creator.name, a.creator.creator_type);
+                println!("type: {}", a.asset_type);
+                if let Some(price) = a.price {
+                    println!("price: {} R$", price);
+                }
+            });
+        }
+        Command::Game { universe_id } => {
+            let game = api_client(&settings)?.get_game_info(universe_id).await?;
+            output(json, &game, |g| {
+                println!("{} #{}", g.name, g.id);
+                println!("creator: {}", g.creator.name);
+                println!("playing: {}  visits: {}", g.playing, g.visits);
+            });
+        }
+        Command::ClientVersion => {
+            let version = api_client(&settings)?.get_client_version().await?;
+            output(json, &version, |v| println!("{}", v));
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_flags(
+    command: FlagsCommand,
+    settings: &DebeonSettings,
+    installation: &InstallTarget,
+    json: bool,
+) -> Result<()> {
+    let manager = RobloxManager::new(settings, http_client()?)?;
+
+    match command {
+        FlagsCommand::Get { keys } => {
+            let mut flags = manager.get_fast_flags(installation)?;
+            if !keys.is_empty() {
+                flags.retain(|key, _| keys.contains(key));
+            }
+            output(json, &flags, |flags| {
+                let mut keys: Vec<_> = flags.keys().collect();
+                keys.sort();
+                for key in keys {
+                    println!("{}={}", key, flags[key]);
+                }
+            });
+        }
+        FlagsCommand::Set { flags, dry_run } => {
+            let flags = parse_assignments(&flags)?;
+            if dry_run {
+                output(json, &manager.preview_fast_flags(installation, &flags)?, print_diff);
+            } else {
+                let applied = manager.set_fast_flags(installation, &flags, AuditSource::Manual)?;
+                output(json, &applied, |result| {
+                    for warning in &result.warnings {
+                        eprintln!("warning: {}", warning.message);
+                    }
+                    println!(
+                        "Set {} flag(s) on {}",
+                        result.applied.len(),
+                        result.installations.join(", ")
+                    );
+                });
+            }
+        }
+        FlagsCommand::Remove(target) => {
+            let selector = match target.pattern {
+                Some(pattern) => FlagSelector::pattern(&pattern)?,
+                None if target.all => FlagSelector::All,
+                None => FlagSelector::Keys(target.keys),
+            };
+            let removal = manager.remove_fast_flags(installation, &selector, AuditSource::Manual)?;
+            output(json, &removal, |removal| {
+                for backup in &removal.backups {
+                    println!("backed up to {}", backup.name);
+                }
+                for key in &removal.removed {
+                    println!("removed {}", key);
+                }
+            });
+        }
+        FlagsCommand::Import { file, profile, dry_run } => {
+            let content = if file.as_os_str() == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(&file)
+                    .with_context(|| format!("Failed to read {}", file.display()))?
+            };
+
+            let import = FlagImporter::parse(&content);
+
+            if !dry_run && !import.flags.is_empty() {
+                match &profile {
+                    Some(profile) => ConfigManager::new()?.merge_custom_flags(profile, &import.flags)?,
+                    None => {
+                        let source = AuditSource::Import { format: import.format };
+                        let flags = import.flags.clone().into_iter().collect();
+                        manager.set_fast_flags(installation, &flags, source)?;
+                    }
+                }
+            }
+
+            output(json, &import, |import| {
+                for entry in &import.skipped {
+                    let location = entry.line.map(|n| format!("line {}: ", n)).unwrap_or_default();
+                    eprintln!("skipped {}{} ({})", location, entry.content.trim(), entry.reason);
+                }
+                for warning in &import.warnings {
+                    eprintln!("warning: {}", warning.message);
+                }
+                let verb = if dry_run { "Would import" } else { "Imported" };
+                println!("{} {} flag(s) from {} input", verb, import.flags.len(), import.format.as_str());
+            });
+        }
+        FlagsCommand::Catalog { search } => {
+            let entries = FlagCatalog::search(search.as_deref().unwrap_or(""));
+            output(json, &entries, |entries| {
+                for entry in entries {
+                    let range = match (entry.min, entry.max) {
+                        (Some(min), Some(max)) => format!("{}..={}", min, max),
+                        _ => String::new(),
+                    };
+                    println!(
+                        "{}\t{}\t{}\t{}\t{}",
+                        entry.name,
+                        entry.kind.as_str(),
+                        entry.category,
+                        range,
+                        entry.description
+                    );
+                }
+            });
+        }
+        FlagsCommand::Diff(target) => {
+            let diff = match (target.backup, target.file) {
+                (Some(backup), _) => manager.diff_backup(installation, &backup)?,
+                (None, Some(file)) => {
+                    let content = std::fs::read_to_string(&file)
+                        .with_context(|| format!("Failed to read {}", file.display()))?;
+                    let other: Value = serde_json::from_str(&content).map_err(|e| {
+                        DebeonError::Validation(format!("{} is not valid JSON: {}", file.display(), e))
+                    })?;
+                    let current: Value = serde_json::to_value(manager.get_fast_flags(installation)?)?;
+                    FlagDiff::between(
+                        other.as_object().context("Expected a JSON object")?,
+                        current.as_object().context("Expected a JSON object")?,
+                    )
+                }
+                (None, None) => unreachable!("clap requires one diff target"),
+            };
+            output(json, &diff, print_diff);
+        }
+        FlagsCommand::Ownership => {
+            output(json, &manager.flag_ownership(installation)?, |ownership| {
+                for key in &ownership.owned {
+                    println!("debeon\t{}", key);
+                }
+                for key in &ownership.hand_edited {
+                    println!("edited\t{}", key);
+                }
+                for key in &ownership.unowned {
+                    println!("user\t{}", key);
+                }
+            });
+        }
+        FlagsCommand::Uninstall => {
+            let removal = manager.uninstall_changes(installation)?;
+            output(json, &removal, print_owned_removal);
+        }
+        FlagsCommand::Remote { application, channel, refresh } => {
+            let target = manager.single_target(installation)?;
+            let flags = manager.get_fast_flags(installation)?;
+            let application = application
+                .unwrap_or_else(|| UpstreamFlags::default_application(&target).to_string());
+            let remote = api_client(settings)?
+                .get_client_settings(&application, &channel, refresh)
+                .await?;
+
+            output(json, &UpstreamFlags::compare(&target.id, &flags, &remote), |comparison| {
+                for flag in &comparison.flags {
+                    let status = match (&flag.upstream, flag.changes_default) {
+                        (None, _) => "missing",
+                        (Some(_), true) => "override",
+                        (Some(_), false) => "default",
+                    };
+                    let upstream = flag.upstream.as_deref().unwrap_or("-");
+                    println!("{}\t{}={}\tupstream={}", status, flag.key, flag.local, upstream);
+                }
+                let stale = if comparison.stale { " (cached, download failed)" } else { "" };
+                println!(
+                    "{} flag(s) not published by Roblox, {} already at the default; {} {} fetched {}{}",
+                    comparison.missing_upstream,
+                    comparison.redundant,
+                    comparison.application,
+                    comparison.channel,
+                    comparison.fetched,
+                    stale
+                );
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn run_profiles(command: ProfilesCommand, json: bool) -> Result<()> {
+    let manager = ConfigManager::new()?;
+
+    match command {
+        ProfilesCommand::List => {
+            let profiles = manager.list_profiles()?;
+            output(json, &profiles, |profiles| {
+                for name in profiles {
+                    println!("{}", name);
+                }
+            });
+        }
+        ProfilesCommand::Show { name } => {
+            let config = manager.load_config(&name)?;
+            output(json, &config, print_json);
+        }
+        ProfilesCommand::Delete { name } => {
+            manager.delete_profile(&name)?;
+            output(json, &format!("Deleted profile {}", name), |m| println!("{}", m));
+        }
+        ProfilesCommand::Rename { name, new_name } => {
+            manager.rename_profile(&name, &new_name)?;
+            output(json, &format!("Renamed {} to {}", name, new_name), |m| println!("{}", m));
+        }
+        ProfilesCommand::Duplicate { name, new_name } => {
+            manager.duplicate_profile(&name, &new_name)?;
+            output(json, &format!("Copied {} to {}", name, new_name), |m| println!("{}", m));
+        }
+        ProfilesCommand::Import { file, name, overwrite } => {
+            let content = std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?;
+            manager.import_config_str(&content, &name, overwrite)?;
+            output(json, &format!("Imported profile {}", name), |m| println!("{}", m));
+        }
+        ProfilesCommand::Export { name, file } => {
+            manager.export_config(&name, &file)?;
+            output(json, &format!("Exported {} to {}", name, file.display()), |m| println!("{}", m));
+        }
+    }
+
+    Ok(())
+}
+
+fn run_presets(command: PresetsCommand, json: bool) -> Result<()> {
+    let library = PresetLibrary::new()?;
+
+    match command {
+        PresetsCommand::List => {
+            let presets = library.list_presets()?;
+            output(json, &presets, |presets| {
+                for p in presets {
+                    let source = if p.builtin { "built-in" } else { "user" };
+                    println!("{}\t{}\t{}\t{}", p.category, p.name, source, p.description);
+                }
+            });
+        }
+        PresetsCommand::Show { name } => {
+            let preset = library.get_preset(&name)?;
+            output(json, &preset, print_json);
+        }
+        PresetsCommand::Save { name, flags, category, description } => {
+            let request = PresetRequest {
+                category,
+                description,
+                flags: parse_assignments(&flags)?.into_iter().collect(),
+            };
+            let preset = library.save_preset(&name, &request)?;
+            output(json, &preset, |p| println!("Saved preset {}", p.name));
+        }
+        PresetsCommand::Delete { name } => {
+            library.delete_preset(&name)?;
+            output(json, &format!("Deleted preset {}", name), |m| println!("{}", m));
+        }
+        PresetsCommand::Combine { presets, profile, flags, policy } => {
+            let config = match profile {
+                Some(name) => Some(ConfigManager::new()?.load_config(&name)?),
+                None => None,
+            };
+            let request = CombineRequest {
+                presets,
+                config,
+                custom_flags: parse_assignments(&flags)?.into_iter().collect(),
+                policy: policy.into(),
+            };
+            let combination = library.combine(&request)?;
+            output(json, &combination, |c| {
+                for conflict in &c.conflicts {
+                    let values: Vec<String> = conflict
+                        .values
+                        .iter()
+                        .map(|v| format!("{}={}", v.source, v.value))
+                        .collect();
+                    println!(
+                        "! {}: {} -> {}",
+                        conflict.key,
+                        values.join(", "),
+                        conflict.resolved_by.as_deref().unwrap_or("-")
+                    );
+                }
+                for (key, value) in &c.flags {
+                    println!("{}={}", key, value);
+                }
+            });
+        }
+    }
+
+    Ok(())
+}
+
+fn run_backups(
+    command: BackupsCommand,
+    settings: &DebeonSettings,
+    installation: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let manager = RobloxManager::new(settings, http_client()?)?;
+    let target = InstallTarget::from_param(installation);
+
+    match command {
+        BackupsCommand::List => {
+            // Listing covers every installation unless one was asked for.
+            let target = if installation.is_some() { target } else { InstallTarget::All };
+            let backups = manager.list_backups(&target)?;
+            output(json, &backups, |backups| {
+                for b in backups {
+                    println!(
+                        "{}\t{}\t{} bytes\t{}",
+                        b.name,
+                        b.created,
+                        b.size,
+                        b.source_installation.as_deref().unwrap_or("-")
+                    );
+                }
+            });
+        }
+        BackupsCommand::Create => {
+            let backup = manager
+                .backup_config(&target)?
+                .ok_or_else(|| DebeonError::NotFound("No ClientAppSettings.json to back up".to_string()))?;
+            output(json, &backup, |b| println!("{}", b.name));
+        }
+        BackupsCommand::Restore { name } => {
+            let installations = manager.restore_config(&target, &name)?;
+            let message = format!("Restored {} to {}", name, installations.join(", "));
+            output(json, &message, |m| println!("{}", m));
+        }
+        BackupsCommand::Diff { name } => {
+            let diff = manager.diff_backup(&target, &name)?;
+            output(json, &diff, print_diff);
+        }
+    }
+
+    Ok(())
+}
+
+fn api_client(settings: &DebeonSettings) -> Result<RobloxApiClient> {
+    RobloxApiClient::new(http_client()?, &settings.api)
+}
+
+fn parse_assignments(assignments: &[String]) -> Result<HashMap<String, String>> {
+    assignments
+        .iter()
+        .map(|assignment| {
+            let (key, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| DebeonError::Validation(format!("Expected KEY=VALUE, got: {}", assignment)))?;
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect()
+}
+
+fn output<T: Serialize>(json: bool, value: &T, human: impl FnOnce(&T)) {
+    if json {
+        print_json(&ApiResponse::success(value));
+    } else {
+        human(value);
+    }
+}
+
+fn print_json<T: Serialize>(value: &T) {
+    match serde_json::to_string_pretty(value) {
+        Ok(text) => println!("{}", text),
+        Err(e) => eprintln!("error: {}", e),
+    }
+}
+
+fn print_diff(diff: &FlagDiff) {
+    for (key, value) in &diff.added {
+        println!("+ {} = {}", key, value);
+    }
+    for (key, value) in &diff.removed {
+        println!("- {} = {}", key, value);
+    }
+    for (key, change) in &diff.changed {
+        println!("~ {}: {} -> {}", key, change.old, change.new);
+    }
+    println!("{} unchanged", diff.unchanged.len());
+    for preview in &diff.user_settings {
+        for change in &preview.changes {
+            let old = change.old.as_deref().unwrap_or("(unset)");
+            println!("~ {} {}: {} -> {}", preview.path, change.name, old, change.new);
+        }
+    }
+    for warning in &diff.warnings {
+        eprintln!("warning: {}", warning.message);
+    }
+}
+
+fn print_owned_removal(removal: &OwnedRemoval) {
+    for backup in &removal.backups {
+        println!("backed up to {}", backup.name);
+    }
+    for key in &removal.removed {
+        println!("removed {}", key);
+    }
+    for key in &removal.hand_edited {
+        println!("kept {} (changed by hand)", key);
+    }
+    if !removal.unowned.is_empty() {
+        println!("kept {} key(s) Debeon did not write", removal.unowned.len());
+    }
+}
+
+fn print_coverage(coverage: &ConfigCoverage) {
+    for field in &coverage.honored {
+        let targets: Vec<&str> = field
+            .flags
+            .iter()
+            .chain(&field.properties)
+            .map(String::as_str)
+            .collect();
+        println!("{} -> {}", field.field, targets.join(", "));
+    }
+    if !coverage.unbacked.is_empty() {
+        let fields: Vec<&str> = coverage.unbacked.iter().map(|f| f.field.as_str()).collect();
+        println!("no backing flag or setting: {}", fields.join(", "));
+    }
+}
+
+fn print_user_settings_writes(writes: &[UserSettingsWrite]) {
+    for write in writes {
+        if let Some(backup) = &write.backup {
+            println!("backed up {} to {}", write.path, backup);
+        }
+        if write.changed.is_empty() {
+            println!("{} already up to date", write.path);
+        } else {
+            println!("updated {}: {}", write.path, write.changed.join(", "));
+        }
+    }
+}
+
+fn print_reapply_report(report: &ReapplyReport) {
+    println!(
+        "{}\t{}\t{}\tprofile: {}\t{}",
+        report.timestamp,
+        report.reason.as_str(),
+        report.installation,
+        report.profile.as_deref().unwrap_or("-"),
+        report.keys.join(", ")
+    );
+}
@@ -0,0 +1,280 @@
+use anyhow::{Result, Context};
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, RequestBuilder};
+use std::path::{Path, PathBuf};
+use std::fs;
+use std::time::{Duration, Instant};
+use crate::error::DebeonError;
+use crate::settings::{ApiSettings, CacheSettings};
+use crate::storage::write_atomic;
+
+/// Minimum time between progress reports while downloading.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Async asset downloader with an on-disk cache. Dropping a returned future
+/// cancels the transfer; partial downloads never reach the cache because files
+/// are only renamed into place once complete.
+pub struct AssetLoader {
+    client: Client,
+    cache_dir: PathBuf,
+    asset_delivery_url: String,
+    thumbnails_url: String,
+    max_cache_bytes: u64,
+    request_timeout: Duration,
+    download_timeout: Duration,
+}
+
+impl AssetLoader {
+    pub fn new(client: Client, api: &ApiSettings, cache: &CacheSettings) -> Result<Self> {
+        let cache_dir = dirs::cache_dir()
+            .context("Failed to get cache directory")?
+            .join("Debeon")
+            .join("assets");
+
+        if !cache_dir.exists() {
+            fs::create_dir_all(&cache_dir)?;
+        }
+
+        Ok(Self {
+            client,
+            cache_dir,
+            asset_delivery_url: api.asset_delivery_url.trim_end_matches('/').to_string(),
+            thumbnails_url: api.thumbnails_url.trim_end_matches('/').to_string(),
+            max_cache_bytes: cache.max_size_mb * 1024 * 1024,
+            request_timeout: Duration::from_secs(api.request_timeout_secs),
+            download_timeout: Duration::from_secs(api.download_timeout_secs),
+        })
+    }
+
+    fn get(&self, url: &str, timeout: Duration) -> RequestBuilder {
+        self.client
+            .get(url)
+            .header(USER_AGENT, "Roblox/WinInet")
+            .timeout(timeout)
+    }
+
+    pub async fn download_asset(&self, asset_id: u64) -> Result<Vec<u8>> {
+        self.download_asset_with_progress(asset_id, |_, _| {}).await
+    }
+
+    /// Downloads an asset, reporting `(downloaded, total)` bytes at most every
+    /// `PROGRESS_INTERVAL` and once more when the transfer completes.
+    pub async fn download_asset_with_progress<F>(&self, asset_id: u64, mut on_progress: F) -> Result<Vec<u8>>
+    where
+        F: FnMut(u64, Option<u64>),
+    {
+        let cache_file = self.cache_dir.join(format!("{}.rbxm", asset_id));
+
+        if let Some(bytes) = read_cached(&cache_file).await? {
+            on_progress(bytes.len() as u64, Some(bytes.len() as u64));
+            return Ok(bytes);
+        }
+
+        let url = format!("{}/asset/?id={}", self.asset_delivery_url, asset_id);
+        let mut response = self.get(&url, self.download_timeout).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to download asset"));
+        }
+
+        let total = response.content_length();
+        let mut bytes = Vec::new();
+        let mut last_report = Instant::now();
+
+        while let Some(chunk) = response.chunk().await? {
+            bytes.extend_from_slice(&chunk);
+            if last_report.elapsed() >= PROGRESS_INTERVAL {
+                on_progress(bytes.len() as u64, total);
+                last_report = Instant::now();
+            }
+        }
+        on_progress(bytes.len() as u64, total);
+
+        self.write_cache(&cache_file, &bytes).await?;
+
+        Ok(bytes)
+    }
+
+    pub async fn download_thumbnail(&self, asset_id: u64, size: ThumbnailSize) -> Result<Vec<u8>> {
+        let size_str = match size {
+            ThumbnailSize::Small => "150x150",
+            ThumbnailSize::Medium => "420x420",
+            ThumbnailSize::Large => "768x432",
+        };
+
+        let cache_file = self.cache_dir.join(format!("{}_{}.png", asset_id, size_str));
+
+        if let Some(bytes) = read_cached(&cache_file).await? {
+            return Ok(bytes);
+        }
+
+        let url = format!(
+            "{}/assets?assetIds={}&size={}&format=Png",
+            self.thumbnails_url,
+            asset_id,
+            size_str
+        );
+
+        let response = self.get(&url, self.request_timeout).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to download thumbnail"));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        if let Some(image_url) = json["data"][0]["imageUrl"].as_str() {
+            let image_response = self.get(image_url, self.download_timeout).send().await?;
+            let bytes = image_response.bytes().await?.to_vec();
+            self.write_cache(&cache_file, &bytes).await?;
+            Ok(bytes)
+        } else {
+            anyhow::bail!(DebeonError::NotFound("No thumbnail URL found".to_string()))
+        }
+    }
+
+    pub async fn download_game_icon(&self, universe_id: u64) -> Result<Vec<u8>> {
+        let cache_file = self.cache_dir.join(format!("game_{}.png", universe_id));
+
+        if let Some(bytes) = read_cached(&cache_file).await? {
+            return Ok(bytes);
+        }
+
+        let url = format!(
+            "{}/games/icons?universeIds={}&size=512x512&format=Png",
+            self.thumbnails_url,
+            universe_id
+        );
+
+        let response = self.get(&url, self.request_timeout).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to download game icon"));
+        }
+
+        let json: serde_json::Value = response.json().await?;
+
+        if let Some(image_url) = json["data"][0]["imageUrl"].as_str() {
+            let image_response = self.get(image_url, self.download_timeout).send().await?;
+            let bytes = image_response.bytes().await?.to_vec();
+            self.write_cache(&cache_file, &bytes).await?;
+            Ok(bytes)
+        } else {
+            anyhow::bail!(DebeonError::NotFound("No game icon URL found".to_string()))
+        }
+    }
+
+    /// Concurrent downloads of the same asset each write their own temp file,
+    /// so one can never rename the other's partial copy into place.
+    async fn write_cache(&self, cache_file: &Path, bytes: &[u8]) -> Result<()> {
+        let cache_file = cache_file.to_path_buf();
+        let bytes = bytes.to_vec();
+        tokio::task::spawn_blocking(move || write_atomic(&cache_file, &bytes)).await??;
+
+        // The asset is cached either way; an oversized cache is trimmed on the
+        // next write.
+        if let Err(e) = self.enforce_cache_limit().await {
+            eprintln!("Failed to trim asset cache {}: {:#}", self.cache_dir.display(), e);
+        }
+        Ok(())
+    }
+
+    pub async fn clear_cache(&self) -> Result<()> {
+        if self.cache_dir.exists() {
+            tokio::fs::remove_dir_all(&self.cache_dir).await?;
+            tokio::fs::create_dir_all(&self.cache_dir).await?;
+        }
+        Ok(())
+    }
+
+    /// Evicts the least recently written files until the cache fits `max_size_mb`.
+    pub async fn enforce_cache_limit(&self) -> Result<()> {
+        if self.max_cache_bytes == 0 {
+            return Ok(());
+        }
+
+        let cache_dir = self.cache_dir.clone();
+        let max_cache_bytes = self.max_cache_bytes;
+
+        tokio::task::spawn_blocking(move || evict_oldest(&cache_dir, max_cache_bytes)).await?
+    }
+
+    pub async fn get_cache_size(&self) -> Result<u64> {
+        let cache_dir = self.cache_dir.clone();
+
+        tokio::task::spawn_blocking(move || {
+            if !cache_dir.exists() {
+                return Ok(0);
+            }
+
+            let mut total_size = 0u64;
+
+            for entry in walkdir::WalkDir::new(&cache_dir) {
+                let entry = entry?;
+                if entry.file_type().is_file() {
+                    total_size += entry.metadata()?.len();
+                }
+            }
+
+            Ok(total_size)
+        })
+        .await?
+    }
+}
+
+async fn read_cached(cache_file: &Path) -> Result<Option<Vec<u8>>> {
+    match tokio::fs::read(cache_file).await {
+        Ok(bytes) => Ok(Some(bytes)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Removes the oldest cached files until the rest fit `max_cache_bytes`.
+/// In-progress temp files are left alone, and files that disappear while the
+/// cache is scanned, such as ones renamed into place or evicted by a
+/// concurrent download, are skipped.
+fn evict_oldest(cache_dir: &Path, max_cache_bytes: u64) -> Result<()> {
+    let mut files = Vec::new();
+    let mut total_size = 0u64;
+
+    for entry in walkdir::WalkDir::new(cache_dir).into_iter().flatten() {
+        let is_temp = entry.file_name().to_string_lossy().ends_with(".tmp");
+        if !entry.file_type().is_file() || is_temp {
+            continue;
+        }
+
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let Ok(modified) = metadata.modified() else {
+            continue;
+        };
+        total_size += metadata.len();
+        files.push((modified, metadata.len(), entry.into_path()));
+    }
+
+    files.sort_by_key(|(modified, _, _)| *modified);
+
+    for (_, size, path) in files {
+        if total_size <= max_cache_bytes {
+            break;
+        }
+
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e).with_context(|| format!("Failed to evict {}", path.display())),
+        }
+        total_size -= size;
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ThumbnailSize {
+    Small,
+    Medium,
+    Large,
+}
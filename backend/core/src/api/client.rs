@@ -0,0 +1,269 @@
+use anyhow::{Result, Context};
+use reqwest::header::USER_AGENT;
+use reqwest::{Client, RequestBuilder};
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use super::{UserInfo, GameInfo, AssetDetails, Creator, RemoteClientSettings};
+use crate::error::DebeonError;
+use crate::settings::ApiSettings;
+use crate::storage::write_atomic;
+
+/// Async client for Roblox's users, games, economy and client settings APIs.
+pub struct RobloxApiClient {
+    client: Client,
+    base_url: String,
+    games_api_url: String,
+    economy_api_url: String,
+    client_settings_url: String,
+    client_settings_cache: PathBuf,
+    client_settings_ttl: chrono::Duration,
+    request_timeout: Duration,
+}
+
+impl RobloxApiClient {
+    /// Takes a clone of the shared pooled client from `api::http_client`.
+    pub fn new(client: Client, settings: &ApiSettings) -> Result<Self> {
+        let client_settings_cache = dirs::cache_dir()
+            .context("Failed to get cache directory")?
+            .join("Debeon")
+            .join("client-settings");
+
+        if !client_settings_cache.exists() {
+            fs::create_dir_all(&client_settings_cache)?;
+        }
+
+        Ok(Self {
+            client,
+            base_url: settings.users_url.trim_end_matches('/').to_string(),
+            games_api_url: settings.games_url.trim_end_matches('/').to_string(),
+            economy_api_url: settings.economy_url.trim_end_matches('/').to_string(),
+            client_settings_url: settings.client_settings_url.trim_end_matches('/').to_string(),
+            client_settings_cache,
+            client_settings_ttl: chrono::Duration::seconds(settings.client_settings_cache_secs as i64),
+            request_timeout: Duration::from_secs(settings.request_timeout_secs),
+        })
+    }
+
+    fn get(&self, url: &str) -> RequestBuilder {
+        self.client
+            .get(url)
+            .header(USER_AGENT, "Debeon/1.0")
+            .timeout(self.request_timeout)
+    }
+
+    pub async fn get_user_info(&self, user_id: u64) -> Result<UserInfo> {
+        let url = format!("{}/users/{}", self.base_url, user_id);
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch user info"));
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(UserInfo {
+            id: data["id"].as_u64().unwrap_or(0),
+            name: data["name"].as_str().unwrap_or("").to_string(),
+            display_name: data["displayName"].as_str().unwrap_or("").to_string(),
+            description: data["description"].as_str().unwrap_or("").to_string(),
+            created: data["created"].as_str().unwrap_or("").to_string(),
+            is_banned: data["isBanned"].as_bool().unwrap_or(false),
+        })
+    }
+
+    pub async fn get_game_info(&self, universe_id: u64) -> Result<GameInfo> {
+        let url = format!("{}/games?universeIds={}", self.games_api_url, universe_id);
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch game info"));
+        }
+
+        let data: Value = response.json().await?;
+
+        let game_data = &data["data"][0];
+
+        Ok(GameInfo {
+            id: game_data["id"].as_u64().unwrap_or(0),
+            name: game_data["name"].as_str().unwrap_or("").to_string(),
+            description: game_data["description"].as_str().unwrap_or("").to_string(),
+            creator: Creator {
+                id: game_data["creator"]["id"].as_u64().unwrap_or(0),
+                name: game_data["creator"]["name"].as_str().unwrap_or("").to_string(),
+                creator_type: game_data["creator"]["type"].as_str().unwrap_or("").to_string(),
+            },
+            price: game_data["price"].as_u64(),
+            playing: game_data["playing"].as_u64().unwrap_or(0),
+            visits: game_data["visits"].as_u64().unwrap_or(0),
+            favorites: game_data["favoritedCount"].as_u64().unwrap_or(0),
+            max_players: game_data["maxPlayers"].as_u64().unwrap_or(0) as u32,
+            genre: game_data["genre"].as_str().unwrap_or("").to_string(),
+        })
+    }
+
+    pub async fn get_asset_details(&self, asset_id: u64) -> Result<AssetDetails> {
+        let url = format!("{}/assets/{}/details", self.economy_api_url, asset_id);
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch asset details"));
+        }
+
+        let data: Value = response.json().await?;
+
+        Ok(AssetDetails {
+            id: data["AssetId"].as_u64().unwrap_or(0),
+            name: data["Name"].as_str().unwrap_or("").to_string(),
+            description: data["Description"].as_str().unwrap_or("").to_string(),
+            asset_type: data["AssetTypeId"].as_u64().unwrap_or(0).to_string(),
+            creator: Creator {
+                id: data["Creator"]["Id"].as_u64().unwrap_or(0),
+                name: data["Creator"]["Name"].as_str().unwrap_or("").to_string(),
+                creator_type: data["Creator"]["CreatorType"].as_str().unwrap_or("").to_string(),
+            },
+            price: data["PriceInRobux"].as_u64(),
+            is_for_sale: data["IsForSale"].as_bool().unwrap_or(false),
+            is_limited: data["IsLimited"].as_bool().unwrap_or(false),
+            is_limited_unique: data["IsLimitedUnique"].as_bool().unwrap_or(false),
+            remaining: data["Remaining"].as_u64(),
+        })
+    }
+
+    pub async fn search_users(&self, keyword: &str) -> Result<Vec<UserInfo>> {
+        let url = format!("{}/users/search?keyword={}&limit=10", self.base_url, keyword);
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to search users"));
+        }
+
+        let data: Value = response.json().await?;
+        let mut users = Vec::new();
+
+        if let Some(user_array) = data["data"].as_array() {
+            for user in user_array {
+                users.push(UserInfo {
+                    id: user["id"].as_u64().unwrap_or(0),
+                    name: user["name"].as_str().unwrap_or("").to_string(),
+                    display_name: user["displayName"].as_str().unwrap_or("").to_string(),
+                    description: String::new(),
+                    created: String::new(),
+                    is_banned: false,
+                });
+            }
+        }
+
+        Ok(users)
+    }
+
+    pub async fn get_client_version(&self) -> Result<String> {
+        let url = format!("{}/client-version/WindowsPlayer", self.client_settings_url);
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch client version"));
+        }
+
+        let data: Value = response.json().await?;
+        Ok(data["clientVersionUpload"].as_str().unwrap_or("unknown").to_string())
+    }
+
+    /// Fast flags Roblox publishes for `application` (e.g. `PCDesktopClient`)
+    /// on `channel` (`live` or a bucket such as `zintegration`). Results are
+    /// cached on disk for `api.client_settings_cache_secs`; `refresh` skips the
+    /// cache. If the download fails, an expired cached copy is returned with
+    /// `stale` set.
+    pub async fn get_client_settings(
+        &self,
+        application: &str,
+        channel: &str,
+        refresh: bool,
+    ) -> Result<RemoteClientSettings> {
+        for (name, value) in [("application", application), ("channel", channel)] {
+            let valid = !value.is_empty()
+                && value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+            if !valid {
+                anyhow::bail!(DebeonError::Validation(format!("Invalid {}: {}", name, value)));
+            }
+        }
+
+        let channel = channel.to_lowercase();
+        let cache_file = self
+            .client_settings_cache
+            .join(format!("{}-{}.json", application, channel));
+        let cached = read_cached_settings(&cache_file).await;
+
+        if let Some(cached) = &cached {
+            let fetched = chrono::DateTime::parse_from_rfc3339(&cached.fetched).ok();
+            let fresh = fetched
+                .map(|fetched| chrono::Local::now().signed_duration_since(fetched) < self.client_settings_ttl)
+                .unwrap_or(false);
+
+            if fresh && !refresh {
+                return Ok(cached.clone());
+            }
+        }
+
+        match self.fetch_client_settings(application, &channel).await {
+            Ok(settings) => {
+                // Concurrent fetches of the same settings each get their own
+                // temp file, so neither can rename the other's half-written copy.
+                let content = serde_json::to_vec_pretty(&settings)?;
+                tokio::task::spawn_blocking(move || write_atomic(&cache_file, &content)).await??;
+                Ok(settings)
+            }
+            Err(e) => match cached {
+                Some(cached) => {
+                    eprintln!("Using cached client settings for {} ({}): {:#}", application, channel, e);
+                    Ok(RemoteClientSettings { stale: true, ..cached })
+                }
+                None => Err(e),
+            },
+        }
+    }
+
+    async fn fetch_client_settings(&self, application: &str, channel: &str) -> Result<RemoteClientSettings> {
+        let mut url = format!("{}/settings/application/{}", self.client_settings_url, application);
+        if channel != "live" {
+            url = format!("{}/bucket/{}", url, channel);
+        }
+
+        let response = self.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch client settings"));
+        }
+
+        let data: Value = response.json().await?;
+        let settings = data["applicationSettings"]
+            .as_object()
+            .ok_or_else(|| DebeonError::Upstream {
+                status: None,
+                message: "Client settings response has no applicationSettings".to_string(),
+            })?
+            .iter()
+            .map(|(key, value)| {
+                let value = match value {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (key.clone(), value)
+            })
+            .collect();
+
+        Ok(RemoteClientSettings {
+            application: application.to_string(),
+            channel: channel.to_string(),
+            fetched: chrono::Local::now().to_rfc3339(),
+            stale: false,
+            settings,
+        })
+    }
+}
+
+async fn read_cached_settings(cache_file: &Path) -> Option<RemoteClientSettings> {
+    let content = tokio::fs::read(cache_file).await.ok()?;
+    serde_json::from_slice(&content).ok()
+}
@@ -0,0 +1,78 @@
+mod client;
+mod asset_loader;
+
+pub use client::RobloxApiClient;
+pub use asset_loader::AssetLoader;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+use utoipa::ToSchema;
+
+/// Builds the pooled HTTP client shared by every Roblox API consumer. Timeouts
+/// are applied per request so downloads and metadata calls can differ.
+pub fn http_client() -> Result<reqwest::Client> {
+    Ok(reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .pool_idle_timeout(Duration::from_secs(90))
+        .build()?)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserInfo {
+    pub id: u64,
+    pub name: String,
+    pub display_name: String,
+    pub description: String,
+    pub created: String,
+    pub is_banned: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GameInfo {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub creator: Creator,
+    pub price: Option<u64>,
+    pub playing: u64,
+    pub visits: u64,
+    pub favorites: u64,
+    pub max_players: u32,
+    pub genre: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct Creator {
+    pub id: u64,
+    pub name: String,
+    pub creator_type: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssetDetails {
+    pub id: u64,
+    pub name: String,
+    pub description: String,
+    pub asset_type: String,
+    pub creator: Creator,
+    pub price: Option<u64>,
+    pub is_for_sale: bool,
+    pub is_limited: bool,
+    pub is_limited_unique: bool,
+    pub remaining: Option<u64>,
+}
+
+/// Fast flags Roblox publishes for one application on one deployment channel.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoteClientSettings {
+    pub application: String,
+    pub channel: String,
+    /// When the settings were downloaded, RFC 3339.
+    pub fetched: String,
+    /// Set when the download failed and an expired cached copy was used.
+    #[serde(default)]
+    pub stale: bool,
+    pub settings: BTreeMap<String, String>,
+}
@@ -0,0 +1,194 @@
+use anyhow::{Result, Context};
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, TimeZone};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::ops::Bound;
+use std::path::{Path, PathBuf};
+use crate::error::DebeonError;
+use crate::models::{AuditChange, AuditEntry, AuditOperation, AuditSource};
+
+const AUDIT_FILE: &str = "audit.jsonl";
+
+/// Append-only record of every `ClientAppSettings.json` write, stored as one
+/// JSON entry per line in the local data dir.
+pub struct AuditLog {
+    path: PathBuf,
+}
+
+/// Narrows `AuditLog::query`. Every set field must match.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub since: Option<DateTime<FixedOffset>>,
+    /// Inclusive for a timestamp, exclusive for the midnight ending a date.
+    pub until: Option<Bound<DateTime<FixedOffset>>>,
+    /// Only entries that changed this flag.
+    pub key: Option<String>,
+    /// Keep at most this many of the newest matches.
+    pub limit: Option<usize>,
+}
+
+impl AuditLog {
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_local_dir()
+            .context("Failed to get local data directory")?
+            .join("Debeon");
+
+        if !data_dir.exists() {
+            fs::create_dir_all(&data_dir)?;
+        }
+
+        Ok(Self { path: data_dir.join(AUDIT_FILE) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends one entry. Each entry is a single write of a single line, so
+    /// the server and the CLI can log to the same file concurrently.
+    pub fn record(&self, entry: &AuditEntry) -> Result<()> {
+        let mut line = serde_json::to_string(entry)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open audit log: {}", self.path.display()))?;
+        file.write_all(line.as_bytes())?;
+
+        Ok(())
+    }
+
+    /// Matching entries, newest first. Lines that fail to parse are skipped.
+    pub fn query(&self, filter: &AuditFilter) -> Result<Vec<AuditEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        let mut entries: Vec<AuditEntry> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .filter(|entry| filter.matches(entry))
+            .collect();
+
+        entries.reverse();
+        if let Some(limit) = filter.limit {
+            entries.truncate(limit);
+        }
+
+        Ok(entries)
+    }
+}
+
+impl AuditFilter {
+    pub fn matches(&self, entry: &AuditEntry) -> bool {
+        if let Some(key) = &self.key {
+            if !entry.changes.contains_key(key) {
+                return false;
+            }
+        }
+
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+
+        let Ok(timestamp) = DateTime::parse_from_rfc3339(&entry.timestamp) else {
+            return false;
+        };
+
+        let after_since = self.since.map(|since| timestamp >= since).unwrap_or(true);
+        let before_until = match self.until {
+            Some(Bound::Included(until)) => timestamp <= until,
+            Some(Bound::Excluded(until)) => timestamp < until,
+            Some(Bound::Unbounded) | None => true,
+        };
+        after_since && before_until
+    }
+}
+
+impl AuditEntry {
+    /// Entry stamped with the current local time, holding only the keys that
+    /// differ between `before` and `after`.
+    pub fn new(
+        operation: AuditOperation,
+        source: AuditSource,
+        installation: String,
+        before: &Map<String, Value>,
+        after: &Map<String, Value>,
+    ) -> Self {
+        let mut changes = BTreeMap::new();
+
+        for key in before.keys().chain(after.keys()) {
+            let (old, new) = (before.get(key), after.get(key));
+            if old != new {
+                changes.insert(
+                    key.clone(),
+                    AuditChange {
+                        before: old.cloned(),
+                        after: new.cloned(),
+                    },
+                );
+            }
+        }
+
+        Self {
+            timestamp: Local::now().to_rfc3339(),
+            operation,
+            source,
+            installation,
+            changes,
+        }
+    }
+}
+
+/// Parses an RFC 3339 timestamp, or a `YYYY-MM-DD` date meaning local
+/// midnight at the start of that day.
+pub fn parse_time(value: &str) -> Result<DateTime<FixedOffset>> {
+    let value = value.trim();
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time);
+    }
+
+    parse_date(value).and_then(local_midnight).ok_or_else(|| invalid_time(value))
+}
+
+/// Parses the end of a time range: an RFC 3339 timestamp, included, or a
+/// `YYYY-MM-DD` date, meaning the whole of that day, up to the next local
+/// midnight.
+pub fn parse_until(value: &str) -> Result<Bound<DateTime<FixedOffset>>> {
+    let value = value.trim();
+
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(Bound::Included(time));
+    }
+
+    parse_date(value)
+        .and_then(|date| date.succ_opt())
+        .and_then(local_midnight)
+        .map(Bound::Excluded)
+        .ok_or_else(|| invalid_time(value))
+}
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+fn local_midnight(date: NaiveDate) -> Option<DateTime<FixedOffset>> {
+    Local
+        .from_local_datetime(&date.and_hms_opt(0, 0, 0)?)
+        .earliest()
+        .map(|time| time.fixed_offset())
+}
+
+fn invalid_time(value: &str) -> anyhow::Error {
+    DebeonError::Validation(format!(
+        "Invalid time: {} (expected RFC 3339 or YYYY-MM-DD)",
+        value
+    ))
+    .into()
+}
@@ -0,0 +1,227 @@
+mod presets;
+
+pub use presets::PresetLibrary;
+
+use anyhow::{Result, Context};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::error::DebeonError;
+use crate::models::RobloxConfig;
+use crate::storage::{write_atomic, FileLock};
+
+/// Saved `RobloxConfig` profiles stored as JSON files under the config dir.
+/// Writes replace files atomically while holding `profiles.lock`, so the
+/// server and the CLI cannot interleave changes to the same profile.
+pub struct ConfigManager {
+    config_dir: PathBuf,
+    profiles_dir: PathBuf,
+}
+
+impl ConfigManager {
+    pub fn new() -> Result<Self> {
+        let config_dir = dirs::config_dir()
+            .context("Failed to get config directory")?
+            .join("Debeon");
+
+        let profiles_dir = config_dir.join("profiles");
+
+        if !config_dir.exists() {
+            fs::create_dir_all(&config_dir)?;
+        }
+
+        if !profiles_dir.exists() {
+            fs::create_dir_all(&profiles_dir)?;
+        }
+
+        Ok(Self {
+            config_dir,
+            profiles_dir,
+        })
+    }
+
+    /// Runs profile work on tokio's blocking pool; waiting for `profiles.lock`
+    /// can take seconds and must not stall async workers.
+    pub async fn run_blocking<T, F>(manager: &Arc<RwLock<Self>>, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T> + Send + 'static,
+    {
+        let manager = manager.clone();
+        tokio::task::spawn_blocking(move || f(&manager.blocking_read())).await?
+    }
+
+    pub fn config_dir(&self) -> &Path {
+        &self.config_dir
+    }
+
+    pub fn save_config(&self, name: &str, config: &RobloxConfig) -> Result<()> {
+        let _lock = self.lock()?;
+        self.write_profile(name, config)
+    }
+
+    pub fn load_config(&self, name: &str) -> Result<RobloxConfig> {
+        let file_path = self.profile_path(name)?;
+
+        if !file_path.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        let content = fs::read_to_string(file_path)?;
+        let config = serde_json::from_str(&content)
+            .with_context(|| format!("Config profile is not valid: {}.json", name))?;
+        Ok(config)
+    }
+
+    pub fn list_profiles(&self) -> Result<Vec<String>> {
+        let mut profiles = Vec::new();
+
+        if !self.profiles_dir.exists() {
+            return Ok(profiles);
+        }
+
+        for entry in fs::read_dir(&self.profiles_dir)? {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str() {
+                if name.ends_with(".json") {
+                    profiles.push(name.trim_end_matches(".json").to_string());
+                }
+            }
+        }
+
+        profiles.sort();
+        Ok(profiles)
+    }
+
+    pub fn delete_profile(&self, name: &str) -> Result<()> {
+        let file_path = self.profile_path(name)?;
+
+        let _lock = self.lock()?;
+
+        if !file_path.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        fs::remove_file(file_path)?;
+        Ok(())
+    }
+
+    pub fn rename_profile(&self, name: &str, new_name: &str) -> Result<()> {
+        let source = self.profile_path(name)?;
+        let destination = self.profile_path(new_name)?;
+        let _lock = self.lock()?;
+
+        if !source.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        if destination.exists() {
+            anyhow::bail!(DebeonError::Conflict(format!("Config profile already exists: {}", new_name)));
+        }
+
+        fs::rename(source, destination)?;
+        Ok(())
+    }
+
+    pub fn duplicate_profile(&self, name: &str, new_name: &str) -> Result<()> {
+        let source = self.profile_path(name)?;
+        let destination = self.profile_path(new_name)?;
+        let _lock = self.lock()?;
+
+        if !source.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        if destination.exists() {
+            anyhow::bail!(DebeonError::Conflict(format!("Config profile already exists: {}", new_name)));
+        }
+
+        write_atomic(&destination, &fs::read(source)?)
+    }
+
+    /// Adds `flags` to a saved profile's custom flags, replacing existing keys.
+    pub fn merge_custom_flags(&self, name: &str, flags: &BTreeMap<String, String>) -> Result<()> {
+        let _lock = self.lock()?;
+        let mut config = self.load_config(name)?;
+        config.custom_flags.extend(flags.clone());
+        self.write_profile(name, &config)
+    }
+
+    pub fn get_default_config(&self) -> RobloxConfig {
+        RobloxConfig::default()
+    }
+
+    pub fn export_config(&self, name: &str, destination: &Path) -> Result<()> {
+        let source = self.profile_path(name)?;
+
+        if !source.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        fs::copy(source, destination)?;
+        Ok(())
+    }
+
+    pub fn import_config(&self, source: &Path, name: &str) -> Result<()> {
+        if !source.exists() {
+            anyhow::bail!(DebeonError::NotFound("Source file not found".to_string()));
+        }
+
+        let content = fs::read_to_string(source)?;
+        let _config: RobloxConfig = serde_json::from_str(&content).map_err(|e| {
+            DebeonError::Validation(format!("{} is not a valid Debeon config: {}", source.display(), e))
+        })?;
+
+        let destination = self.profile_path(name)?;
+        let _lock = self.lock()?;
+        write_atomic(&destination, content.as_bytes())
+    }
+
+    pub fn export_config_string(&self, name: &str) -> Result<String> {
+        let source = self.profile_path(name)?;
+
+        if !source.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Config profile not found: {}", name)));
+        }
+
+        Ok(fs::read_to_string(source)?)
+    }
+
+    pub fn import_config_str(&self, content: &str, name: &str, overwrite: bool) -> Result<()> {
+        let config: RobloxConfig = serde_json::from_str(content).map_err(|e| {
+            DebeonError::Validation(format!("Uploaded profile is not a valid Debeon config: {}", e))
+        })?;
+
+        let _lock = self.lock()?;
+
+        if !overwrite && self.profile_path(name)?.exists() {
+            anyhow::bail!(DebeonError::Conflict(format!("Config profile already exists: {}", name)));
+        }
+
+        self.write_profile(name, &config)
+    }
+
+    /// Held around every profile write. Callers must not nest it: a second
+    /// acquire from the same process waits like any other writer.
+    fn lock(&self) -> Result<FileLock> {
+        FileLock::acquire(&self.profiles_dir)
+    }
+
+    fn write_profile(&self, name: &str, config: &RobloxConfig) -> Result<()> {
+        let json = serde_json::to_string_pretty(config)?;
+        write_atomic(&self.profile_path(name)?, json.as_bytes())
+    }
+
+    fn profile_path(&self, name: &str) -> Result<PathBuf> {
+        let is_plain_name = !name.is_empty()
+            && Path::new(name).file_name().map(|n| n == name).unwrap_or(false);
+
+        if !is_plain_name {
+            anyhow::bail!(DebeonError::Validation(format!("Invalid profile name: {}", name)));
+        }
+
+        Ok(self.profiles_dir.join(format!("{}.json", name)))
+    }
+}
@@ -0,0 +1,189 @@
+use anyhow::{Result, Context};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use crate::error::DebeonError;
+use crate::models::{CombineRequest, FlagCombination, FlagPreset, PresetRequest};
+use crate::roblox::{combine_flag_sources, FastFlag, FlagManager, FlagSource};
+use crate::storage::{write_atomic, FileLock};
+
+/// Built-in flag presets plus user presets stored as JSON files under the
+/// config dir. Built-ins are read-only and their names are reserved.
+pub struct PresetLibrary {
+    presets_dir: PathBuf,
+}
+
+impl PresetLibrary {
+    pub fn new() -> Result<Self> {
+        let presets_dir = dirs::config_dir()
+            .context("Failed to get config directory")?
+            .join("Debeon")
+            .join("presets");
+
+        if !presets_dir.exists() {
+            fs::create_dir_all(&presets_dir)?;
+        }
+
+        Ok(Self { presets_dir })
+    }
+
+    /// Runs preset file work on tokio's blocking pool.
+    pub async fn run_blocking<T, F>(presets: &Arc<Self>, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T> + Send + 'static,
+    {
+        let presets = presets.clone();
+        tokio::task::spawn_blocking(move || f(&presets)).await?
+    }
+
+    pub fn presets_dir(&self) -> &Path {
+        &self.presets_dir
+    }
+
+    /// Built-in presets followed by user presets, each group sorted by category
+    /// and name.
+    pub fn list_presets(&self) -> Result<Vec<FlagPreset>> {
+        let mut builtin = FlagManager::builtin_presets();
+        builtin.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+
+        let mut user = Vec::new();
+
+        for entry in fs::read_dir(&self.presets_dir)? {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str() {
+                // Unreadable preset files are skipped so one bad file does not
+                // hide the rest of the library.
+                if let Some(Ok(preset)) = name.strip_suffix(".json").map(|n| self.load_user_preset(n)) {
+                    user.push(preset);
+                }
+            }
+        }
+
+        user.sort_by(|a, b| (&a.category, &a.name).cmp(&(&b.category, &b.name)));
+
+        builtin.extend(user);
+        Ok(builtin)
+    }
+
+    pub fn get_preset(&self, name: &str) -> Result<FlagPreset> {
+        if let Some(preset) = Self::builtin(name) {
+            return Ok(preset);
+        }
+
+        let file_path = self.preset_path(name)?;
+
+        if !file_path.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Preset not found: {}", name)));
+        }
+
+        self.load_user_preset(name)
+    }
+
+    /// Creates or replaces a user preset. Every flag must be valid for its
+    /// prefix, and built-in names cannot be reused.
+    pub fn save_preset(&self, name: &str, request: &PresetRequest) -> Result<FlagPreset> {
+        if Self::builtin(name).is_some() {
+            anyhow::bail!(DebeonError::Conflict(format!("Cannot overwrite built-in preset: {}", name)));
+        }
+
+        if request.flags.is_empty() {
+            anyhow::bail!(DebeonError::Validation("A preset needs at least one flag".to_string()));
+        }
+
+        FastFlag::parse_all(&request.flags)?;
+
+        let preset = FlagPreset {
+            name: name.to_string(),
+            category: if request.category.trim().is_empty() {
+                "Custom".to_string()
+            } else {
+                request.category.trim().to_string()
+            },
+            description: request.description.clone(),
+            flags: request.flags.clone(),
+            builtin: false,
+        };
+
+        let file_path = self.preset_path(name)?;
+        let json = serde_json::to_string_pretty(&preset)?;
+        let _lock = self.lock()?;
+        write_atomic(&file_path, json.as_bytes())?;
+
+        Ok(preset)
+    }
+
+    pub fn delete_preset(&self, name: &str) -> Result<()> {
+        if Self::builtin(name).is_some() {
+            anyhow::bail!(DebeonError::Conflict(format!("Cannot delete built-in preset: {}", name)));
+        }
+
+        let file_path = self.preset_path(name)?;
+        let _lock = self.lock()?;
+
+        if !file_path.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Preset not found: {}", name)));
+        }
+
+        fs::remove_file(file_path)?;
+        Ok(())
+    }
+
+    /// Held around every preset write. Callers must not nest it: a second
+    /// acquire from the same process waits like any other writer.
+    fn lock(&self) -> Result<FileLock> {
+        FileLock::acquire(&self.presets_dir)
+    }
+
+    /// Combines the request's config, presets (in order), the config's custom
+    /// flags and the request's custom flags under its conflict policy.
+    pub fn combine(&self, request: &CombineRequest) -> Result<FlagCombination> {
+        let mut sources = Vec::new();
+
+        if let Some(config) = &request.config {
+            sources.push(FlagSource::config(config)?);
+        }
+
+        for name in &request.presets {
+            sources.push(FlagSource::preset(&self.get_preset(name)?)?);
+        }
+
+        if let Some(config) = request.config.as_ref().filter(|c| !c.custom_flags.is_empty()) {
+            let flags: BTreeMap<String, String> = config.custom_flags.clone().into_iter().collect();
+            sources.push(FlagSource::custom("config:custom", &flags)?);
+        }
+        if !request.custom_flags.is_empty() {
+            sources.push(FlagSource::custom("custom", &request.custom_flags)?);
+        }
+
+        combine_flag_sources(&sources, request.policy)
+    }
+
+    fn builtin(name: &str) -> Option<FlagPreset> {
+        FlagManager::builtin_presets()
+            .into_iter()
+            .find(|preset| preset.name.eq_ignore_ascii_case(name))
+    }
+
+    fn load_user_preset(&self, name: &str) -> Result<FlagPreset> {
+        let content = fs::read_to_string(self.preset_path(name)?)?;
+        let mut preset: FlagPreset = serde_json::from_str(&content)
+            .with_context(|| format!("Preset file is not valid: {}.json", name))?;
+
+        preset.name = name.to_string();
+        preset.builtin = false;
+        Ok(preset)
+    }
+
+    fn preset_path(&self, name: &str) -> Result<PathBuf> {
+        let is_plain_name = !name.is_empty()
+            && Path::new(name).file_name().map(|n| n == name).unwrap_or(false);
+
+        if !is_plain_name {
+            anyhow::bail!(DebeonError::Validation(format!("Invalid preset name: {}", name)));
+        }
+
+        Ok(self.presets_dir.join(format!("{}.json", name)))
+    }
+}
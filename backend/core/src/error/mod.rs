@@ -0,0 +1,108 @@
+use thiserror::Error;
+use reqwest::StatusCode;
+
+/// Errors surfaced to API clients. Lower layers keep using `anyhow` and raise
+/// these variants where the distinction matters; anything else is classified
+/// by `From<anyhow::Error>`. Parsing client input raises `Validation` at the
+/// parse site, so a corrupt file Debeon owns is reported as `Internal`.
+#[derive(Debug, Error)]
+pub enum DebeonError {
+    #[error("{0}")]
+    NotFound(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("{message}")]
+    Upstream { status: Option<u16>, message: String },
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Conflict(String),
+
+    #[error("No Roblox installation found")]
+    NoInstallation,
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl DebeonError {
+    pub fn upstream(status: reqwest::StatusCode, action: &str) -> Self {
+        let message = format!("{}: {}", action, status);
+
+        if status == reqwest::StatusCode::NOT_FOUND {
+            return DebeonError::NotFound(message);
+        }
+
+        DebeonError::Upstream {
+            status: Some(status.as_u16()),
+            message,
+        }
+    }
+
+    /// Stable machine-readable code placed in `ApiResponse::code`.
+    pub fn code(&self) -> &'static str {
+        match self {
+            DebeonError::NotFound(_) => "not_found",
+            DebeonError::Validation(_) => "validation",
+            DebeonError::Upstream { status: Some(429), .. } => "upstream_rate_limited",
+            DebeonError::Upstream { .. } => "upstream",
+            DebeonError::Io(_) => "io",
+            DebeonError::Conflict(_) => "conflict",
+            DebeonError::NoInstallation => "no_installation",
+            DebeonError::Unauthorized(_) => "unauthorized",
+            DebeonError::Internal(_) => "internal",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            DebeonError::NotFound(_) => StatusCode::NOT_FOUND,
+            DebeonError::Validation(_) => StatusCode::BAD_REQUEST,
+            DebeonError::Upstream { status: Some(429), .. } => StatusCode::TOO_MANY_REQUESTS,
+            DebeonError::Upstream { status: Some(503), .. } => StatusCode::SERVICE_UNAVAILABLE,
+            DebeonError::Upstream { .. } => StatusCode::BAD_GATEWAY,
+            DebeonError::Io(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            DebeonError::Conflict(_) => StatusCode::CONFLICT,
+            DebeonError::NoInstallation => StatusCode::NOT_FOUND,
+            DebeonError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            DebeonError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl From<reqwest::Error> for DebeonError {
+    fn from(error: reqwest::Error) -> Self {
+        DebeonError::Upstream {
+            status: error.status().map(|s| s.as_u16()),
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for DebeonError {
+    fn from(error: anyhow::Error) -> Self {
+        let error = match error.downcast::<DebeonError>() {
+            Ok(error) => return error,
+            Err(error) => error,
+        };
+
+        let error = match error.downcast::<std::io::Error>() {
+            Ok(error) => return DebeonError::Io(error),
+            Err(error) => error,
+        };
+
+        let error = match error.downcast::<reqwest::Error>() {
+            Ok(error) => return error.into(),
+            Err(error) => error,
+        };
+
+        DebeonError::Internal(format!("{:#}", error))
+    }
+}
@@ -0,0 +1,82 @@
+mod watcher;
+
+pub use watcher::SettingsWatcher;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use utoipa::ToSchema;
+use crate::models::{BackupInfo, ReapplyReport, RobloxInstallation};
+
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DebeonEvent {
+    ConfigApplied,
+    FlagsChanged { keys: Vec<String> },
+    SettingsModifiedExternally { path: String },
+    InstallationAdded { installation: RobloxInstallation },
+    InstallationRemoved { installation: RobloxInstallation },
+    DownloadProgress { asset_id: u64, downloaded: u64, total: Option<u64> },
+    BackupCreated { backup: BackupInfo },
+    BackupRestored { name: String },
+    SettingsReapplied { report: ReapplyReport },
+    /// Sent only on an event stream that fell behind: `count` events were
+    /// skipped, so clients should refetch whatever state they track.
+    EventsDropped { count: u64 },
+}
+
+impl DebeonEvent {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            DebeonEvent::ConfigApplied => "config_applied",
+            DebeonEvent::FlagsChanged { .. } => "flags_changed",
+            DebeonEvent::SettingsModifiedExternally { .. } => "settings_modified_externally",
+            DebeonEvent::InstallationAdded { .. } => "installation_added",
+            DebeonEvent::InstallationRemoved { .. } => "installation_removed",
+            DebeonEvent::DownloadProgress { .. } => "download_progress",
+            DebeonEvent::BackupCreated { .. } => "backup_created",
+            DebeonEvent::BackupRestored { .. } => "backup_restored",
+            DebeonEvent::SettingsReapplied { .. } => "settings_reapplied",
+            DebeonEvent::EventsDropped { .. } => "events_dropped",
+        }
+    }
+
+    /// Whether the event follows a write Debeon made to `ClientAppSettings.json`.
+    pub fn writes_settings(&self) -> bool {
+        matches!(
+            self,
+            DebeonEvent::ConfigApplied
+                | DebeonEvent::FlagsChanged { .. }
+                | DebeonEvent::BackupRestored { .. }
+                | DebeonEvent::SettingsReapplied { .. }
+        )
+    }
+}
+
+#[derive(Clone)]
+pub struct EventBus {
+    sender: broadcast::Sender<DebeonEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self { sender }
+    }
+
+    pub fn publish(&self, event: DebeonEvent) {
+        // No subscribers is not an error, the event is simply dropped.
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DebeonEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
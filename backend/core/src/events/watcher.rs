@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use crate::models::{ReapplyReason, RobloxInstallation};
+use crate::roblox::{content_hash, RobloxDetector, RobloxManager};
+use super::{DebeonEvent, EventBus};
+
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Polls the installation list and every installation's
+/// `ClientAppSettings.json` and publishes changes that Debeon did not make
+/// itself. With re-apply enabled, it also writes the remembered profile and
+/// flags back into new version directories and rewritten settings files.
+pub struct SettingsWatcher {
+    roblox_manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
+    reapply: bool,
+    installations: HashMap<String, RobloxInstallation>,
+    /// Settings file hash per installation ID; `None` when there is no file.
+    settings_hashes: HashMap<String, Option<String>>,
+}
+
+impl SettingsWatcher {
+    pub fn new(roblox_manager: Arc<RwLock<RobloxManager>>, events: EventBus, reapply: bool) -> Self {
+        Self {
+            roblox_manager,
+            events,
+            reapply,
+            installations: HashMap::new(),
+            settings_hashes: HashMap::new(),
+        }
+    }
+
+    pub fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut receiver = self.events.subscribe();
+            let mut interval = tokio::time::interval(POLL_INTERVAL);
+
+            self.installations = self.scan_installations().await;
+
+            // Updates installed while the server was not running.
+            let installations: Vec<_> = self.installations.values().cloned().collect();
+            for installation in &installations {
+                self.reapply(installation, ReapplyReason::Startup).await;
+            }
+
+            self.rehash_all().await;
+
+            loop {
+                tokio::select! {
+                    event = receiver.recv() => match event {
+                        Ok(event) if event.writes_settings() => self.rehash_all().await,
+                        // Missed events may have included Debeon's own writes.
+                        Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => self.rehash_all().await,
+                        Ok(_) => {}
+                        Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                    },
+                    _ = interval.tick() => self.poll().await,
+                }
+            }
+        })
+    }
+
+    async fn poll(&mut self) {
+        let installations = self.scan_installations().await;
+
+        // IDs survive Roblox updates, so a new version directory shows up as
+        // the same ID with a different path.
+        let same = |a: Option<&RobloxInstallation>, b: &RobloxInstallation| {
+            a.is_some_and(|a| a.path == b.path)
+        };
+
+        for (id, installation) in &self.installations {
+            if !same(installations.get(id), installation) {
+                self.events.publish(DebeonEvent::InstallationRemoved {
+                    installation: installation.clone(),
+                });
+            }
+        }
+
+        let previous = std::mem::replace(&mut self.installations, installations);
+        self.settings_hashes.retain(|id, _| self.installations.contains_key(id));
+
+        let current: Vec<_> = self.installations.values().cloned().collect();
+        for installation in &current {
+            if !same(previous.get(&installation.id), installation) {
+                self.events.publish(DebeonEvent::InstallationAdded {
+                    installation: installation.clone(),
+                });
+                self.reapply(installation, ReapplyReason::NewInstallation).await;
+                self.rehash(installation).await;
+                continue;
+            }
+
+            let hash = hash_settings(installation).await;
+            if self.settings_hashes.get(&installation.id) == Some(&hash) {
+                continue;
+            }
+
+            // A Debeon write whose event has not arrived yet.
+            if self.roblox_manager.read().await.wrote_last(&installation.id, hash.as_deref()) {
+                self.settings_hashes.insert(installation.id.clone(), hash);
+                continue;
+            }
+
+            if hash.is_some() {
+                self.events.publish(DebeonEvent::SettingsModifiedExternally {
+                    path: RobloxDetector::client_settings_file(Path::new(&installation.path))
+                        .to_string_lossy()
+                        .to_string(),
+                });
+            }
+
+            self.reapply(installation, ReapplyReason::ExternalRewrite).await;
+            self.rehash(installation).await;
+        }
+    }
+
+    /// Re-applies remembered settings if enabled, logging and publishing what
+    /// was written. Failures are logged and retried on the next change.
+    async fn reapply(&self, installation: &RobloxInstallation, reason: ReapplyReason) {
+        if !self.reapply {
+            return;
+        }
+
+        let target = installation.clone();
+        let result =
+            RobloxManager::run_blocking(&self.roblox_manager, move |m| m.reapply(&target, reason))
+                .await;
+
+        match result {
+            Ok(Some(report)) => {
+                println!(
+                    "Re-applied {} setting(s) to {} ({})",
+                    report.keys.len(),
+                    report.installation,
+                    reason.as_str()
+                );
+                self.events.publish(DebeonEvent::SettingsReapplied { report });
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Failed to re-apply settings to {}: {:#}", installation.id, e),
+        }
+    }
+
+    async fn scan_installations(&self) -> HashMap<String, RobloxInstallation> {
+        RobloxManager::run_blocking(&self.roblox_manager, |m| m.find_installations())
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|i| (i.id.clone(), i))
+            .collect()
+    }
+
+    async fn rehash(&mut self, installation: &RobloxInstallation) {
+        let hash = hash_settings(installation).await;
+        self.settings_hashes.insert(installation.id.clone(), hash);
+    }
+
+    async fn rehash_all(&mut self) {
+        let installations: Vec<_> = self.installations.values().cloned().collect();
+        for installation in &installations {
+            self.rehash(installation).await;
+        }
+    }
+}
+
+async fn hash_settings(installation: &RobloxInstallation) -> Option<String> {
+    let settings_file = RobloxDetector::client_settings_file(Path::new(&installation.path));
+    let content = tokio::fs::read(settings_file).await.ok()?;
+    Some(content_hash(&content))
+}
@@ -0,0 +1,47 @@
+//! Core Roblox configuration logic shared by the Debeon HTTP server and CLI.
+//!
+//! The main entry points are:
+//!
+//! - [`RobloxManager`] for installation discovery, applying a [`models::RobloxConfig`]
+//!   to one installation or all of them ([`InstallTarget`]),
+//!   reading and writing fast flags, and managing `ClientAppSettings.json` backups.
+//!   Each write it makes is appended to the [`audit::AuditLog`].
+//! - [`ConfigManager`] for the saved profile library and [`PresetLibrary`] for
+//!   built-in and user flag presets.
+//! - [`RobloxApiClient`] and [`AssetLoader`] for Roblox's public web APIs.
+//! - [`DebeonSettings`] for the layered `settings.toml` configuration every
+//!   component is constructed from.
+//!
+//! Errors are returned as `anyhow::Error`; convert them with
+//! `DebeonError::from` to get a stable code and HTTP status.
+//!
+//! ```no_run
+//! use debeon_core::{api::http_client, DebeonSettings, InstallTarget, RobloxManager};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let settings = DebeonSettings::load_from(std::env::vars(), &[])?;
+//! let manager = RobloxManager::new(&settings, http_client()?)?;
+//! for (key, value) in manager.get_fast_flags(&InstallTarget::Primary)? {
+//!     println!("{} = {}", key, value);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod api;
+pub mod audit;
+pub mod config;
+pub mod error;
+pub mod events;
+pub mod models;
+pub mod roblox;
+pub mod settings;
+pub mod storage;
+
+pub use api::{AssetLoader, RobloxApiClient};
+pub use config::{ConfigManager, PresetLibrary};
+pub use error::DebeonError;
+pub use roblox::{
+    ConfigPatcher, FastFlag, FlagManager, InstallTarget, RobloxDetector, RobloxManager,
+};
+pub use settings::DebeonSettings;
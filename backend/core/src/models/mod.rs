@@ -0,0 +1,814 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use std::collections::{BTreeMap, HashMap};
+use crate::error::DebeonError;
+use crate::roblox::FlagKind;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct RobloxConfig {
+    pub graphics: GraphicsConfig,
+    pub audio: AudioConfig,
+    pub controls: ControlsConfig,
+    pub network: NetworkConfig,
+    pub rendering: RenderingConfig,
+    pub performance: PerformanceConfig,
+    pub ui: UIConfig,
+    pub custom_flags: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct GraphicsConfig {
+    pub graphics_quality: u8,
+    pub render_distance: u32,
+    pub shadow_quality: u8,
+    pub texture_quality: u8,
+    pub particle_quality: u8,
+    pub vsync: bool,
+    pub fullscreen: bool,
+    pub resolution_width: u32,
+    pub resolution_height: u32,
+    pub anti_aliasing: u8,
+    pub anisotropic_filtering: u8,
+}
+
+impl Default for GraphicsConfig {
+    fn default() -> Self {
+        Self {
+            graphics_quality: 10,
+            render_distance: 1000,
+            shadow_quality: 3,
+            texture_quality: 3,
+            particle_quality: 3,
+            vsync: true,
+            fullscreen: false,
+            resolution_width: 1920,
+            resolution_height: 1080,
+            anti_aliasing: 4,
+            anisotropic_filtering: 16,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AudioConfig {
+    pub master_volume: f32,
+    pub music_volume: f32,
+    pub sfx_volume: f32,
+    pub voice_volume: f32,
+    pub spatial_audio: bool,
+    pub output_device: String,
+    pub input_device: String,
+}
+
+impl Default for AudioConfig {
+    fn default() -> Self {
+        Self {
+            master_volume: 0.8,
+            music_volume: 0.7,
+            sfx_volume: 0.8,
+            voice_volume: 0.9,
+            spatial_audio: true,
+            output_device: String::from("default"),
+            input_device: String::from("default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ControlsConfig {
+    pub mouse_sensitivity: f32,
+    pub invert_y_axis: bool,
+    pub camera_mode: String,
+    pub key_bindings: HashMap<String, String>,
+    pub gamepad_enabled: bool,
+    pub gamepad_sensitivity: f32,
+}
+
+impl Default for ControlsConfig {
+    fn default() -> Self {
+        let mut bindings = HashMap::new();
+        bindings.insert("forward".to_string(), "W".to_string());
+        bindings.insert("backward".to_string(), "S".to_string());
+        bindings.insert("left".to_string(), "A".to_string());
+        bindings.insert("right".to_string(), "D".to_string());
+        bindings.insert("jump".to_string(), "Space".to_string());
+
+        Self {
+            mouse_sensitivity: 0.5,
+            invert_y_axis: false,
+            camera_mode: String::from("follow"),
+            key_bindings: bindings,
+            gamepad_enabled: false,
+            gamepad_sensitivity: 0.5,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct NetworkConfig {
+    pub preferred_region: String,
+    pub max_ping: u32,
+    pub connection_quality: String,
+    pub enable_ipv6: bool,
+    pub data_usage_limit: Option<u64>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            preferred_region: String::from("auto"),
+            max_ping: 200,
+            connection_quality: String::from("high"),
+            enable_ipv6: true,
+            data_usage_limit: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RenderingConfig {
+    pub frame_rate_limit: Option<u32>,
+    pub dynamic_lighting: bool,
+    pub post_processing: bool,
+    pub bloom: bool,
+    pub depth_of_field: bool,
+    pub motion_blur: bool,
+    pub ambient_occlusion: bool,
+    pub reflections: bool,
+    pub global_illumination: bool,
+}
+
+impl Default for RenderingConfig {
+    fn default() -> Self {
+        Self {
+            frame_rate_limit: Some(60),
+            dynamic_lighting: true,
+            post_processing: true,
+            bloom: true,
+            depth_of_field: false,
+            motion_blur: false,
+            ambient_occlusion: true,
+            reflections: true,
+            global_illumination: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PerformanceConfig {
+    pub low_latency_mode: bool,
+    pub power_saving_mode: bool,
+    pub background_performance: String,
+    pub memory_limit_mb: Option<u32>,
+    pub cpu_affinity: Vec<usize>,
+    pub gpu_preference: String,
+}
+
+impl Default for PerformanceConfig {
+    fn default() -> Self {
+        Self {
+            low_latency_mode: false,
+            power_saving_mode: false,
+            background_performance: String::from("normal"),
+            memory_limit_mb: None,
+            cpu_affinity: vec![],
+            gpu_preference: String::from("high_performance"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UIConfig {
+    pub ui_scale: f32,
+    pub show_fps: bool,
+    pub show_ping: bool,
+    pub chat_enabled: bool,
+    pub gui_transparency: f32,
+    pub theme: String,
+    pub custom_cursor: Option<String>,
+}
+
+impl Default for UIConfig {
+    fn default() -> Self {
+        Self {
+            ui_scale: 1.0,
+            show_fps: false,
+            show_ping: false,
+            chat_enabled: true,
+            gui_transparency: 0.0,
+            theme: String::from("dark"),
+            custom_cursor: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RobloxInstallation {
+    /// Stable identifier accepted by the `installation` parameter.
+    pub id: String,
+    pub path: String,
+    pub version: String,
+    pub channel: String,
+    pub last_modified: String,
+    /// Whether this is the most recently modified installation, used when no
+    /// installation is given.
+    pub primary: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ProfileNameRequest {
+    pub new_name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct BackupInfo {
+    pub name: String,
+    pub size: u64,
+    pub created: String,
+    pub source_installation: Option<String>,
+}
+
+/// Known fast flag with its type, purpose and accepted range.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagCatalogEntry {
+    pub name: String,
+    pub kind: FlagKind,
+    pub category: String,
+    pub description: String,
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagWarningKind {
+    UnknownFlag,
+    OutOfRange,
+}
+
+/// Non-fatal problem with a flag that is still written as requested.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagWarning {
+    pub key: String,
+    pub kind: FlagWarningKind,
+    pub message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagsApplied {
+    pub applied: Vec<String>,
+    pub warnings: Vec<FlagWarning>,
+    /// IDs of the installations that were written.
+    pub installations: Vec<String>,
+}
+
+/// Source format recognised by the flag importer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportFormat {
+    /// A flat JSON object of flags, as in Bloxstrap's and Fishstrap's
+    /// `ClientAppSettings.json`.
+    Json,
+    /// Sober's `config.json`, with flags under `fflags`.
+    Sober,
+    /// One `KEY=VALUE` or `"KEY": VALUE` pair per line.
+    KeyValue,
+}
+
+impl ImportFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ImportFormat::Json => "json",
+            ImportFormat::Sober => "sober",
+            ImportFormat::KeyValue => "key_value",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SkippedEntry {
+    /// 1-based line number, for line-based formats
+    pub line: Option<usize>,
+    pub content: String,
+    pub reason: String,
+}
+
+/// Flags recovered from an import, with everything that could not be used.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagImport {
+    pub format: ImportFormat,
+    pub flags: BTreeMap<String, String>,
+    pub skipped: Vec<SkippedEntry>,
+    pub warnings: Vec<FlagWarning>,
+}
+
+/// A named set of fast flags. Built-in presets ship with Debeon; user presets
+/// live as JSON files in the config dir.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagPreset {
+    pub name: String,
+    pub category: String,
+    pub description: String,
+    pub flags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct PresetRequest {
+    #[serde(default)]
+    pub category: String,
+    #[serde(default)]
+    pub description: String,
+    pub flags: BTreeMap<String, String>,
+}
+
+/// How `combine_flag_sources` settles a key that sources set to different values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictPolicy {
+    /// Refuse to combine while any conflict exists.
+    #[default]
+    Error,
+    /// Later sources win: config, then presets in the order given, then the
+    /// config's custom flags, then the request's custom flags.
+    LastWins,
+    /// Presets win over the config and custom flags.
+    PreferPreset,
+    /// Custom flags win over presets and the config.
+    PreferCustom,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagSourceValue {
+    pub source: String,
+    pub value: serde_json::Value,
+}
+
+/// A key that two or more sources set to different values.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagConflict {
+    pub key: String,
+    pub values: Vec<FlagSourceValue>,
+    /// The source whose value was kept under the chosen policy.
+    pub resolved_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct FlagCombination {
+    pub flags: BTreeMap<String, String>,
+    pub conflicts: Vec<FlagConflict>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct CombineRequest {
+    /// Preset names, applied in this order
+    #[serde(default)]
+    pub presets: Vec<String>,
+    /// Config whose mapped flags and custom flags take part in the analysis
+    pub config: Option<RobloxConfig>,
+    #[serde(default)]
+    pub custom_flags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub policy: ConflictPolicy,
+}
+
+/// Result of a flag remove operation, with the backups taken beforehand (one
+/// per installation that had matching flags).
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagRemoval {
+    pub removed: Vec<String>,
+    pub backups: Vec<BackupInfo>,
+}
+
+/// How the keys in one installation's `ClientAppSettings.json` split between
+/// Debeon and the user.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct FlagOwnership {
+    pub installation: String,
+    /// Keys Debeon wrote that still hold the value it wrote.
+    pub owned: Vec<String>,
+    /// Keys Debeon wrote that have since been changed by hand.
+    pub hand_edited: Vec<String>,
+    /// Keys Debeon never wrote.
+    pub unowned: Vec<String>,
+}
+
+/// How one local flag relates to what Roblox publishes for it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoteFlagStatus {
+    pub key: String,
+    pub local: String,
+    /// Published value, `None` when Roblox no longer ships the flag.
+    pub upstream: Option<String>,
+    pub exists_upstream: bool,
+    /// Whether the local value differs from the published one. Flags missing
+    /// upstream count as changing nothing Roblox still reads.
+    pub changes_default: bool,
+}
+
+/// Local flags of one installation checked against Roblox's published client
+/// settings.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct RemoteComparison {
+    pub installation: String,
+    pub application: String,
+    pub channel: String,
+    /// When the published settings were downloaded, RFC 3339.
+    pub fetched: String,
+    /// Set when the download failed and an expired cached copy was used.
+    pub stale: bool,
+    pub flags: Vec<RemoteFlagStatus>,
+    /// Local flags Roblox no longer publishes.
+    pub missing_upstream: usize,
+    /// Local flags set to the value Roblox already ships.
+    pub redundant: usize,
+}
+
+/// Result of removing Debeon's keys when uninstalling its changes or
+/// switching profile. Hand-edited and unowned keys are left in place.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct OwnedRemoval {
+    pub installations: Vec<String>,
+    /// Keys Debeon wrote that were removed.
+    pub removed: Vec<String>,
+    /// Keys Debeon wrote but that were changed by hand; kept and no longer
+    /// treated as Debeon's.
+    pub hand_edited: Vec<String>,
+    /// Keys Debeon never wrote; kept.
+    pub unowned: Vec<String>,
+    /// Backups taken before anything was removed, one per installation.
+    pub backups: Vec<BackupInfo>,
+    /// Which fields of the new config were written, when switching profile.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub coverage: Option<ConfigCoverage>,
+    /// User settings files updated for the new config, when switching profile.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_settings: Vec<UserSettingsWrite>,
+}
+
+/// A `RobloxConfig` field written as fast flags, Roblox user settings, or
+/// both.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MappedField {
+    /// Dotted path of the field, e.g. `graphics.vsync`.
+    pub field: String,
+    pub flags: Vec<String>,
+    /// `UserGameSettings` properties in the user settings XML.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub properties: Vec<String>,
+}
+
+/// A `RobloxConfig` field that neither a fast flag nor a user setting
+/// controls.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UnbackedField {
+    pub field: String,
+    pub reason: String,
+}
+
+/// Which typed `RobloxConfig` fields reach `ClientAppSettings.json` or the
+/// user settings XML.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ConfigCoverage {
+    pub honored: Vec<MappedField>,
+    /// Optional fields left unset, whose flags are not touched.
+    pub unset: Vec<String>,
+    pub unbacked: Vec<UnbackedField>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ConfigApplied {
+    /// IDs of the installations that were written.
+    pub installations: Vec<String>,
+    pub coverage: ConfigCoverage,
+    /// User settings files updated alongside `ClientAppSettings.json`.
+    pub user_settings: Vec<UserSettingsWrite>,
+}
+
+/// Data returned by `POST /api/apply`: the writes made, the pending changes
+/// with `dry_run`, or the previous profile's keys removed with `switch`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum ApplyResult {
+    Applied(ConfigApplied),
+    Preview(FlagDiff),
+    Switched(OwnedRemoval),
+}
+
+/// Data returned by `POST /api/flags`: the flags written, or the pending
+/// changes with `dry_run`.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum SetFlagsResult {
+    Applied(FlagsApplied),
+    Preview(FlagDiff),
+}
+
+/// One property of Roblox's user settings XML.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSetting {
+    /// Element type, e.g. `float`, `bool` or `token`.
+    pub kind: String,
+    /// Raw element text; nested elements are kept as XML.
+    pub value: String,
+}
+
+/// The `UserGameSettings` properties of an installation's user settings file.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSettings {
+    pub path: String,
+    pub properties: BTreeMap<String, UserSetting>,
+}
+
+/// A `UserGameSettings` property a config would set.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSettingChange {
+    pub name: String,
+    /// Current text, or `None` when the file does not have the property yet.
+    pub old: Option<String>,
+    pub new: String,
+}
+
+/// What applying a config would change in one user settings file.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSettingsPreview {
+    pub path: String,
+    /// Only the properties whose value would change.
+    pub changes: Vec<UserSettingChange>,
+}
+
+/// Result of applying a config to one user settings file.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserSettingsWrite {
+    pub path: String,
+    /// Properties whose value changed; empty when the file was left as is.
+    pub changed: Vec<String>,
+    /// Copy of the file taken before it was changed.
+    pub backup: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FlagChange {
+    pub old: serde_json::Value,
+    pub new: serde_json::Value,
+}
+
+/// Key-level difference between two `ClientAppSettings.json` objects.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct FlagDiff {
+    pub added: BTreeMap<String, serde_json::Value>,
+    pub removed: BTreeMap<String, serde_json::Value>,
+    pub changed: BTreeMap<String, FlagChange>,
+    pub unchanged: Vec<String>,
+    /// Catalog warnings for the flags being written, only set on previews.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<FlagWarning>,
+    /// Pending changes to Roblox's user settings XML, only set on config
+    /// previews when the installation has a user settings file.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub user_settings: Vec<UserSettingsPreview>,
+}
+
+impl FlagDiff {
+    pub fn between(
+        old: &serde_json::Map<String, serde_json::Value>,
+        new: &serde_json::Map<String, serde_json::Value>,
+    ) -> Self {
+        let mut diff = Self::default();
+
+        for (key, old_value) in old {
+            match new.get(key) {
+                Some(new_value) if new_value == old_value => diff.unchanged.push(key.clone()),
+                Some(new_value) => {
+                    diff.changed.insert(
+                        key.clone(),
+                        FlagChange {
+                            old: old_value.clone(),
+                            new: new_value.clone(),
+                        },
+                    );
+                }
+                None => {
+                    diff.removed.insert(key.clone(), old_value.clone());
+                }
+            }
+        }
+
+        for (key, new_value) in new {
+            if !old.contains_key(key) {
+                diff.added.insert(key.clone(), new_value.clone());
+            }
+        }
+
+        diff.unchanged.sort();
+        diff
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditOperation {
+    ApplyConfig,
+    SetFlags,
+    RemoveFlags,
+    RestoreBackup,
+    Reapply,
+    RemoveOwned,
+}
+
+impl AuditOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditOperation::ApplyConfig => "apply_config",
+            AuditOperation::SetFlags => "set_flags",
+            AuditOperation::RemoveFlags => "remove_flags",
+            AuditOperation::RestoreBackup => "restore_backup",
+            AuditOperation::Reapply => "reapply",
+            AuditOperation::RemoveOwned => "remove_owned",
+        }
+    }
+}
+
+/// What asked for a `ClientAppSettings.json` write.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AuditSource {
+    Manual,
+    Profile { name: String },
+    Preset { name: String },
+    Import { format: ImportFormat },
+    Restore { backup: String },
+    Reapply { reason: ReapplyReason },
+}
+
+impl std::fmt::Display for AuditSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AuditSource::Manual => write!(f, "manual"),
+            AuditSource::Profile { name } => write!(f, "profile:{}", name),
+            AuditSource::Preset { name } => write!(f, "preset:{}", name),
+            AuditSource::Import { format } => write!(f, "import:{}", format.as_str()),
+            AuditSource::Restore { backup } => write!(f, "restore:{}", backup),
+            AuditSource::Reapply { reason } => write!(f, "reapply:{}", reason.as_str()),
+        }
+    }
+}
+
+/// Value of one key before and after a write; `None` means the key was absent.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChange {
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// One record of the append-only audit log.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    /// RFC 3339 time of the write.
+    pub timestamp: String,
+    pub operation: AuditOperation,
+    pub source: AuditSource,
+    /// Installation directory whose settings were written.
+    pub installation: String,
+    /// Only the keys whose value changed.
+    pub changes: BTreeMap<String, AuditChange>,
+}
+
+/// Why the watcher re-applied remembered settings to an installation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReapplyReason {
+    /// The installation was already there when the server started.
+    Startup,
+    /// A new version directory appeared, usually after a Roblox update.
+    NewInstallation,
+    /// `ClientAppSettings.json` was rewritten or deleted by something other
+    /// than Debeon.
+    ExternalRewrite,
+    /// Requested with `debeon reapply --now`.
+    Manual,
+}
+
+impl ReapplyReason {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReapplyReason::Startup => "startup",
+            ReapplyReason::NewInstallation => "new_installation",
+            ReapplyReason::ExternalRewrite => "external_rewrite",
+            ReapplyReason::Manual => "manual",
+        }
+    }
+}
+
+/// The profile and flags last written to one channel's installations, which
+/// the watcher puts back when they go missing.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct AppliedState {
+    /// Name of the last applied profile, if the config came from one.
+    pub profile: Option<String>,
+    pub config: Option<RobloxConfig>,
+    /// Flags set since, applied on top of `config`.
+    pub flags: BTreeMap<String, String>,
+    /// RFC 3339 time of the last change.
+    pub updated: String,
+}
+
+impl AppliedState {
+    pub fn is_empty(&self) -> bool {
+        self.config.is_none() && self.flags.is_empty()
+    }
+}
+
+/// One re-apply made by the watcher.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ReapplyReport {
+    /// RFC 3339 time of the write.
+    pub timestamp: String,
+    pub installation: String,
+    pub channel: String,
+    pub reason: ReapplyReason,
+    pub profile: Option<String>,
+    /// Keys that were missing or had drifted and were written back.
+    pub keys: Vec<String>,
+}
+
+/// What the watcher remembers per channel and what it re-applied recently.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReapplyStatus {
+    /// Whether the server watcher re-applies settings (`watcher.reapply`).
+    #[serde(default)]
+    pub enabled: bool,
+    pub channels: BTreeMap<String, AppliedState>,
+    /// Most recent first.
+    pub history: Vec<ReapplyReport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AssetInfo {
+    pub asset_id: u64,
+    pub name: String,
+    pub description: String,
+    pub creator: String,
+    pub asset_type: String,
+    pub created: String,
+    pub updated: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[aliases(
+    MessageResponse = ApiResponse<String>,
+    ProfilesResponse = ApiResponse<Vec<String>>,
+    ConfigResponse = ApiResponse<RobloxConfig>,
+    InstallationsResponse = ApiResponse<Vec<RobloxInstallation>>,
+    FlagsResponse = ApiResponse<HashMap<String, String>>,
+    BackupResponse = ApiResponse<BackupInfo>,
+    BackupsResponse = ApiResponse<Vec<BackupInfo>>,
+    FlagDiffResponse = ApiResponse<FlagDiff>,
+    FlagRemovalResponse = ApiResponse<FlagRemoval>,
+    FlagOwnershipResponse = ApiResponse<FlagOwnership>,
+    OwnedRemovalResponse = ApiResponse<OwnedRemoval>,
+    ConfigAppliedResponse = ApiResponse<ConfigApplied>,
+    ApplyResultResponse = ApiResponse<ApplyResult>,
+    SetFlagsResultResponse = ApiResponse<SetFlagsResult>,
+    UserSettingsResponse = ApiResponse<UserSettings>,
+    RemoteComparisonResponse = ApiResponse<RemoteComparison>,
+    PresetResponse = ApiResponse<FlagPreset>,
+    FlagCombinationResponse = ApiResponse<FlagCombination>,
+    FlagCatalogResponse = ApiResponse<Vec<FlagCatalogEntry>>,
+    FlagsAppliedResponse = ApiResponse<FlagsApplied>,
+    FlagImportResponse = ApiResponse<FlagImport>,
+    PresetsResponse = ApiResponse<Vec<FlagPreset>>,
+    AuditEntriesResponse = ApiResponse<Vec<AuditEntry>>,
+    ReapplyStatusResponse = ApiResponse<ReapplyStatus>,
+    UserResponse = ApiResponse<crate::api::UserInfo>,
+    AssetResponse = ApiResponse<crate::api::AssetDetails>,
+)]
+pub struct ApiResponse<T> {
+    pub success: bool,
+    pub data: Option<T>,
+    pub error: Option<String>,
+    pub code: Option<String>,
+}
+
+impl<T> ApiResponse<T> {
+    pub fn success(data: T) -> Self {
+        Self {
+            success: true,
+            data: Some(data),
+            error: None,
+            code: None,
+        }
+    }
+
+    pub fn error(error: &DebeonError) -> Self {
+        Self {
+            success: false,
+            data: None,
+            error: Some(error.to_string()),
+            code: Some(error.code().to_string()),
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::models::{AppliedState, ReapplyReport, ReapplyStatus, RobloxConfig};
+use crate::storage::{write_atomic, FileLock};
+use super::patcher::ConfigPatcher;
+
+const APPLIED_FILE: &str = "applied.json";
+const HISTORY_LIMIT: usize = 50;
+
+/// Remembers, per Roblox channel, the profile and flags Debeon last wrote so
+/// they can be put back after an update replaces the version directory.
+/// Stored in the local data dir and shared by the server and the CLI.
+pub struct AppliedStore {
+    path: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct AppliedFile {
+    channels: BTreeMap<String, AppliedState>,
+    history: Vec<ReapplyReport>,
+}
+
+impl AppliedStore {
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_local_dir()
+            .context("Failed to get local data directory")?
+            .join("Debeon");
+
+        if !data_dir.exists() {
+            fs::create_dir_all(&data_dir)?;
+        }
+
+        Ok(Self { path: data_dir.join(APPLIED_FILE) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Remembered state for every channel plus the re-apply history. The
+    /// caller fills in `enabled`.
+    pub fn status(&self) -> Result<ReapplyStatus> {
+        let file = self.load()?;
+
+        Ok(ReapplyStatus {
+            enabled: false,
+            channels: file.channels,
+            history: file.history,
+        })
+    }
+
+    pub fn state(&self, channel: &str) -> Result<Option<AppliedState>> {
+        Ok(self.load()?.channels.remove(channel).filter(|state| !state.is_empty()))
+    }
+
+    /// Replaces the remembered config. Flags the config sets itself are
+    /// dropped so they cannot override it on the next re-apply.
+    pub fn record_config(&self, channel: &str, profile: Option<String>, config: &RobloxConfig) -> Result<()> {
+        let keys: Vec<String> = ConfigPatcher::config_flags(config)?
+            .iter()
+            .map(|flag| flag.name().to_string())
+            .collect();
+
+        self.update_channel(channel, |state| {
+            state.profile = profile;
+            state.config = Some(config.clone());
+            state.flags.retain(|key, _| !keys.contains(key));
+        })
+    }
+
+    pub fn record_flags(&self, channel: &str, flags: &HashMap<String, String>) -> Result<()> {
+        self.update_channel(channel, |state| {
+            state.flags.extend(flags.iter().map(|(k, v)| (k.clone(), v.clone())));
+        })
+    }
+
+    pub fn forget_flags(&self, channel: &str, keys: &[String]) -> Result<()> {
+        self.update_channel(channel, |state| {
+            state.flags.retain(|key, _| !keys.contains(key));
+        })
+    }
+
+    /// Forgets everything remembered for `channel`, e.g. after a backup was
+    /// restored over its settings on purpose.
+    pub fn forget(&self, channel: &str) -> Result<()> {
+        self.update(|file| {
+            file.channels.remove(channel);
+        })
+    }
+
+    pub fn push_report(&self, report: ReapplyReport) -> Result<()> {
+        self.update(|file| {
+            file.history.insert(0, report);
+            file.history.truncate(HISTORY_LIMIT);
+        })
+    }
+
+    fn update_channel(&self, channel: &str, change: impl FnOnce(&mut AppliedState)) -> Result<()> {
+        self.update(|file| {
+            let state = file.channels.entry(channel.to_string()).or_default();
+            change(state);
+            state.updated = chrono::Local::now().to_rfc3339();
+        })
+    }
+
+    fn update(&self, change: impl FnOnce(&mut AppliedFile)) -> Result<()> {
+        let _lock = FileLock::acquire(&self.path)?;
+        let mut file = self.load()?;
+        change(&mut file);
+        write_atomic(&self.path, serde_json::to_string_pretty(&file)?.as_bytes())
+    }
+
+    fn load(&self) -> Result<AppliedFile> {
+        if !self.path.exists() {
+            return Ok(AppliedFile::default());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid applied settings file: {}", self.path.display()))
+    }
+}
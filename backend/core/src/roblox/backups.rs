@@ -0,0 +1,208 @@
+use anyhow::{Result, Context};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use crate::error::DebeonError;
+use crate::models::{BackupInfo, FlagDiff};
+use crate::storage::write_atomic;
+use super::detector::RobloxDetector;
+use super::settings_file::SettingsFile;
+
+/// Copies of `ClientAppSettings.json` kept in the local data dir, each with a
+/// `.meta` sidecar recording when and where it was taken.
+#[derive(Debug, Clone)]
+pub struct BackupStore {
+    backup_dir: PathBuf,
+}
+
+impl BackupStore {
+    pub fn new() -> Result<Self> {
+        let backup_dir = dirs::data_local_dir()
+            .context("Failed to get local data directory")?
+            .join("Debeon")
+            .join("backups");
+
+        if !backup_dir.exists() {
+            fs::create_dir_all(&backup_dir)?;
+        }
+
+        Ok(Self { backup_dir })
+    }
+
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+
+    /// Backs up the installation's settings file as is, whether or not it
+    /// parses. Returns `None` when there is no file to back up.
+    pub fn create(&self, install: &Path) -> Result<Option<BackupInfo>> {
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        if !settings_file.exists() {
+            return Ok(None);
+        }
+
+        let now = chrono::Local::now();
+        let stamp = now.format("%Y%m%d_%H%M%S").to_string();
+        let mut name = format!("backup_{}.json", stamp);
+        let mut suffix = 1;
+        while self.backup_dir.join(&name).exists() {
+            name = format!("backup_{}_{}.json", stamp, suffix);
+            suffix += 1;
+        }
+        let backup_file = self.backup_dir.join(&name);
+
+        fs::copy(settings_file, &backup_file)?;
+
+        let metadata = BackupMetadata {
+            created: now.format("%Y-%m-%d %H:%M:%S").to_string(),
+            source_installation: Some(install.to_string_lossy().to_string()),
+        };
+        write_atomic(
+            &self.backup_dir.join(format!("{}.meta", name)),
+            serde_json::to_string_pretty(&metadata)?.as_bytes(),
+        )?;
+
+        Ok(Some(self.info(&name)?))
+    }
+
+    /// Copies a Roblox user settings XML file into the `user-settings`
+    /// subfolder and returns the copy's name. These copies are not listed as
+    /// settings backups and are restored by hand.
+    pub fn create_user_settings(&self, settings_file: &Path) -> Result<String> {
+        let dir = self.backup_dir.join("user-settings");
+        fs::create_dir_all(&dir)?;
+
+        let stem = settings_file
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| "user_settings".to_string());
+        let stamp = chrono::Local::now().format("%Y%m%d_%H%M%S").to_string();
+
+        let mut name = format!("{}_{}.xml", stem, stamp);
+        let mut suffix = 1;
+        while dir.join(&name).exists() {
+            name = format!("{}_{}_{}.xml", stem, stamp, suffix);
+            suffix += 1;
+        }
+
+        fs::copy(settings_file, dir.join(&name))
+            .with_context(|| format!("Failed to back up {}", settings_file.display()))?;
+        Ok(name)
+    }
+
+    pub fn restore(&self, install: &Path, backup_name: &str) -> Result<()> {
+        self.restore_to(&SettingsFile::lock(install)?, backup_name)
+    }
+
+    pub(crate) fn restore_to(&self, settings: &SettingsFile, backup_name: &str) -> Result<()> {
+        let content = fs::read(self.path(backup_name)?)?;
+
+        settings.read_or_back_up(self)?;
+        settings.write_raw(&content)
+    }
+
+    pub fn list_names(&self) -> Result<Vec<String>> {
+        let mut backups = Vec::new();
+
+        if !self.backup_dir.exists() {
+            return Ok(backups);
+        }
+
+        for entry in fs::read_dir(&self.backup_dir)? {
+            let entry = entry?;
+            if let Some(name) = entry.file_name().to_str() {
+                if name.ends_with(".json") {
+                    backups.push(name.to_string());
+                }
+            }
+        }
+
+        backups.sort();
+        backups.reverse();
+
+        Ok(backups)
+    }
+
+    pub fn list(&self) -> Result<Vec<BackupInfo>> {
+        self.list_names()?
+            .iter()
+            .map(|name| self.info(name))
+            .collect()
+    }
+
+    pub fn info(&self, backup_name: &str) -> Result<BackupInfo> {
+        let backup_file = self.path(backup_name)?;
+        let file_metadata = fs::metadata(&backup_file)?;
+
+        let sidecar = self.backup_dir.join(format!("{}.meta", backup_name));
+        let metadata = fs::read_to_string(sidecar)
+            .ok()
+            .and_then(|content| serde_json::from_str::<BackupMetadata>(&content).ok())
+            .unwrap_or_else(|| {
+                let modified: chrono::DateTime<chrono::Local> = file_metadata
+                    .modified()
+                    .map(Into::into)
+                    .unwrap_or_else(|_| chrono::Local::now());
+
+                BackupMetadata {
+                    created: modified.format("%Y-%m-%d %H:%M:%S").to_string(),
+                    source_installation: None,
+                }
+            });
+
+        Ok(BackupInfo {
+            name: backup_name.to_string(),
+            size: file_metadata.len(),
+            created: metadata.created,
+            source_installation: metadata.source_installation,
+        })
+    }
+
+    pub fn diff(&self, install: &Path, backup_name: &str) -> Result<FlagDiff> {
+        let backup_file = self.path(backup_name)?;
+        let content = fs::read_to_string(backup_file)?;
+        let backup: Value = serde_json::from_str(&content).context("Backup is not valid JSON")?;
+
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        let current = if settings_file.exists() {
+            let content = fs::read_to_string(&settings_file)?;
+            serde_json::from_str::<Value>(&content).context("ClientAppSettings.json is not valid JSON")?
+        } else {
+            serde_json::json!({})
+        };
+
+        let empty = Map::new();
+        Ok(FlagDiff::between(
+            backup.as_object().unwrap_or(&empty),
+            current.as_object().unwrap_or(&empty),
+        ))
+    }
+
+    fn path(&self, backup_name: &str) -> Result<PathBuf> {
+        let is_plain_name = Path::new(backup_name)
+            .file_name()
+            .map(|n| n == backup_name)
+            .unwrap_or(false);
+
+        if !is_plain_name || !backup_name.ends_with(".json") {
+            anyhow::bail!(DebeonError::Validation(format!("Invalid backup name: {}", backup_name)));
+        }
+
+        let backup_file = self.backup_dir.join(backup_name);
+
+        if !backup_file.exists() {
+            anyhow::bail!(DebeonError::NotFound(format!("Backup file not found: {}", backup_name)));
+        }
+
+        Ok(backup_file)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupMetadata {
+    created: String,
+    source_installation: Option<String>,
+}
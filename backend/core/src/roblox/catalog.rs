@@ -0,0 +1,163 @@
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use crate::models::{FlagCatalogEntry, FlagWarning, FlagWarningKind};
+use super::fast_flag::{FastFlag, FlagKind, FlagValue};
+
+/// Largest edit distance at which a catalog flag is offered as a suggestion.
+const MAX_SUGGESTION_DISTANCE: usize = 3;
+const MAX_SUGGESTIONS: usize = 3;
+
+#[derive(Deserialize)]
+struct CatalogRecord {
+    name: String,
+    category: String,
+    description: String,
+    min: Option<i64>,
+    max: Option<i64>,
+}
+
+static CATALOG: Lazy<Vec<FlagCatalogEntry>> = Lazy::new(|| {
+    let records: Vec<CatalogRecord> = serde_json::from_str(include_str!("catalog.json"))
+        .expect("bundled catalog.json is valid");
+
+    let mut entries: Vec<FlagCatalogEntry> = records
+        .into_iter()
+        .map(|record| FlagCatalogEntry {
+            kind: FlagKind::from_name(&record.name)
+                .expect("catalog.json only lists flags with a known prefix"),
+            name: record.name,
+            category: record.category,
+            description: record.description,
+            min: record.min,
+            max: record.max,
+        })
+        .collect();
+
+    entries.sort_by(|a, b| a.name.cmp(&b.name));
+    entries
+});
+
+/// Bundled list of known fast flags, used to catch typos and out-of-range
+/// values before they are written.
+pub struct FlagCatalog;
+
+impl FlagCatalog {
+    pub fn entries() -> &'static [FlagCatalogEntry] {
+        &CATALOG
+    }
+
+    pub fn get(name: &str) -> Option<&'static FlagCatalogEntry> {
+        CATALOG.iter().find(|entry| entry.name == name)
+    }
+
+    /// Entries whose name, category or description contains `query`,
+    /// ignoring case. An empty query returns the whole catalog.
+    pub fn search(query: &str) -> Vec<FlagCatalogEntry> {
+        let query = query.trim().to_lowercase();
+
+        CATALOG
+            .iter()
+            .filter(|entry| {
+                query.is_empty()
+                    || entry.name.to_lowercase().contains(&query)
+                    || entry.category.to_lowercase().contains(&query)
+                    || entry.description.to_lowercase().contains(&query)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Catalog names closest to `name` by case-insensitive edit distance.
+    pub fn suggest(name: &str) -> Vec<String> {
+        let name = name.to_lowercase();
+
+        let mut candidates: Vec<(usize, &str)> = CATALOG
+            .iter()
+            .map(|entry| (edit_distance(&name, &entry.name.to_lowercase()), entry.name.as_str()))
+            .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+            .collect();
+
+        candidates.sort();
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, name)| name.to_string())
+            .collect()
+    }
+
+    /// Warnings for flags missing from the catalog or outside its range.
+    pub fn check(flags: &[FastFlag]) -> Vec<FlagWarning> {
+        let mut warnings = Vec::new();
+
+        for flag in flags {
+            let Some(entry) = Self::get(flag.name()) else {
+                let suggestions = Self::suggest(flag.name());
+                let message = if suggestions.is_empty() {
+                    format!("{} is not in the flag catalog", flag.name())
+                } else {
+                    format!(
+                        "{} is not in the flag catalog; did you mean {}?",
+                        flag.name(),
+                        suggestions.join(", ")
+                    )
+                };
+
+                warnings.push(FlagWarning {
+                    key: flag.name().to_string(),
+                    kind: FlagWarningKind::UnknownFlag,
+                    message,
+                    suggestions,
+                });
+                continue;
+            };
+
+            let value = match flag.value() {
+                FlagValue::Int(value) => *value,
+                FlagValue::LogLevel(level) => i64::from(*level),
+                _ => continue,
+            };
+
+            let below = entry.min.map(|min| value < min).unwrap_or(false);
+            let above = entry.max.map(|max| value > max).unwrap_or(false);
+
+            if below || above {
+                let range = match (entry.min, entry.max) {
+                    (Some(min), Some(max)) => format!("{} to {}", min, max),
+                    (Some(min), None) => format!("at least {}", min),
+                    (None, Some(max)) => format!("at most {}", max),
+                    (None, None) => unreachable!("out of range requires a bound"),
+                };
+
+                warnings.push(FlagWarning {
+                    key: flag.name().to_string(),
+                    kind: FlagWarningKind::OutOfRange,
+                    message: format!("{} = {} is outside the expected range {}", flag.name(), value, range),
+                    suggestions: Vec::new(),
+                });
+            }
+        }
+
+        warnings.sort_by(|a, b| a.key.cmp(&b.key));
+        warnings
+    }
+}
+
+/// Levenshtein distance over chars.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, a_char) in a.chars().enumerate() {
+        current[0] = i + 1;
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,219 @@
+use anyhow::Result;
+use std::collections::{BTreeMap, HashMap};
+use crate::error::DebeonError;
+use crate::models::{
+    ConflictPolicy, FlagCombination, FlagConflict, FlagPreset, FlagSourceValue, RobloxConfig,
+};
+use super::fast_flag::FastFlag;
+use super::patcher::ConfigPatcher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagSourceKind {
+    Config,
+    Preset,
+    Custom,
+}
+
+/// One contributor of flags to a combination: the fields of a `RobloxConfig`,
+/// a preset, or hand-entered custom flags.
+#[derive(Debug, Clone)]
+pub struct FlagSource {
+    pub name: String,
+    pub kind: FlagSourceKind,
+    pub flags: Vec<FastFlag>,
+}
+
+impl FlagSource {
+    /// Flags mapped from the typed config fields. Its custom flags belong in
+    /// a separate `FlagSource::custom`.
+    pub fn config(config: &RobloxConfig) -> Result<Self> {
+        Ok(Self {
+            name: "config".to_string(),
+            kind: FlagSourceKind::Config,
+            flags: ConfigPatcher::mapped_flags(config)?,
+        })
+    }
+
+    pub fn preset(preset: &FlagPreset) -> Result<Self> {
+        Ok(Self {
+            name: format!("preset:{}", preset.name),
+            kind: FlagSourceKind::Preset,
+            flags: FastFlag::parse_all(&preset.flags)?,
+        })
+    }
+
+    /// Hand-entered flags. `name` tells apart the config's own custom flags
+    /// from those in the request, so a disagreement between them is reported.
+    pub fn custom(name: &str, flags: &BTreeMap<String, String>) -> Result<Self> {
+        Ok(Self {
+            name: name.to_string(),
+            kind: FlagSourceKind::Custom,
+            flags: FastFlag::parse_all(flags)?,
+        })
+    }
+}
+
+/// Keys that sources set to different values, in key order. Values are
+/// compared after typing, so `"1"` and `"true"` for an `FFlag` agree.
+pub fn find_conflicts(sources: &[FlagSource]) -> Vec<FlagConflict> {
+    let mut by_key: BTreeMap<&str, Vec<(&FlagSource, &FastFlag)>> = BTreeMap::new();
+
+    for source in sources {
+        for flag in &source.flags {
+            by_key.entry(flag.name()).or_default().push((source, flag));
+        }
+    }
+
+    by_key
+        .into_iter()
+        .filter(|(_, entries)| entries.iter().any(|(_, flag)| flag.value() != entries[0].1.value()))
+        .map(|(key, entries)| FlagConflict {
+            key: key.to_string(),
+            values: entries
+                .iter()
+                .map(|(source, flag)| FlagSourceValue {
+                    source: source.name.clone(),
+                    value: flag.to_json(),
+                })
+                .collect(),
+            resolved_by: None,
+        })
+        .collect()
+}
+
+/// Merges `sources` into one flag set, settling conflicts with `policy`.
+/// Sources are expected in application order: config, presets, custom.
+pub fn combine_flag_sources(sources: &[FlagSource], policy: ConflictPolicy) -> Result<FlagCombination> {
+    let mut conflicts = find_conflicts(sources);
+
+    if policy == ConflictPolicy::Error && !conflicts.is_empty() {
+        let details: Vec<String> = conflicts
+            .iter()
+            .map(|conflict| {
+                let values: Vec<String> = conflict
+                    .values
+                    .iter()
+                    .map(|v| format!("{}={}", v.source, v.value))
+                    .collect();
+                format!("{} ({})", conflict.key, values.join(", "))
+            })
+            .collect();
+
+        anyhow::bail!(DebeonError::Conflict(format!(
+            "Conflicting flags: {}",
+            details.join("; ")
+        )));
+    }
+
+    // Each key keeps the value from the highest ranked source; ties go to the
+    // later source.
+    let rank = |kind: FlagSourceKind| match (policy, kind) {
+        (ConflictPolicy::PreferPreset, FlagSourceKind::Preset) => 1,
+        (ConflictPolicy::PreferCustom, FlagSourceKind::Custom) => 1,
+        _ => 0,
+    };
+
+    let mut winners: HashMap<&str, (u8, &FlagSource, &FastFlag)> = HashMap::new();
+
+    for source in sources {
+        for flag in &source.flags {
+            let candidate_rank = rank(source.kind);
+            let replace = winners
+                .get(flag.name())
+                .map(|(current_rank, _, _)| candidate_rank >= *current_rank)
+                .unwrap_or(true);
+
+            if replace {
+                winners.insert(flag.name(), (candidate_rank, source, flag));
+            }
+        }
+    }
+
+    for conflict in &mut conflicts {
+        conflict.resolved_by = winners
+            .get(conflict.key.as_str())
+            .map(|(_, source, _)| source.name.clone());
+    }
+
+    let flags = winners
+        .into_iter()
+        .map(|(key, (_, _, flag))| (key.to_string(), flag.value().to_string()))
+        .collect();
+
+    Ok(FlagCombination { flags, conflicts })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(name: &str, kind: FlagSourceKind, flags: &[(&str, &str)]) -> FlagSource {
+        FlagSource {
+            name: name.to_string(),
+            kind,
+            flags: flags.iter().map(|(key, value)| FastFlag::parse(key, value).unwrap()).collect(),
+        }
+    }
+
+    fn sources() -> Vec<FlagSource> {
+        vec![
+            source("config", FlagSourceKind::Config, &[("FIntFps", "60"), ("FFlagShared", "true")]),
+            source("preset:a", FlagSourceKind::Preset, &[("FIntFps", "144"), ("FFlagShared", "1")]),
+            source("custom", FlagSourceKind::Custom, &[("FIntFps", "240")]),
+            source("preset:b", FlagSourceKind::Preset, &[("FIntFps", "120")]),
+        ]
+    }
+
+    #[test]
+    fn typed_values_that_agree_do_not_conflict() {
+        let conflicts = find_conflicts(&sources());
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "FIntFps");
+        assert_eq!(conflicts[0].values.len(), 4);
+    }
+
+    #[test]
+    fn error_policy_refuses_conflicts() {
+        let error = combine_flag_sources(&sources(), ConflictPolicy::Error).unwrap_err();
+
+        assert!(matches!(error.downcast_ref::<DebeonError>(), Some(DebeonError::Conflict(_))));
+    }
+
+    #[test]
+    fn error_policy_combines_without_conflicts() {
+        let sources = [source("config", FlagSourceKind::Config, &[("FIntFps", "60")])];
+        let combination = combine_flag_sources(&sources, ConflictPolicy::Error).unwrap();
+
+        assert_eq!(combination.flags["FIntFps"], "60");
+        assert!(combination.conflicts.is_empty());
+    }
+
+    #[test]
+    fn last_wins_keeps_the_later_source() {
+        let combination = combine_flag_sources(&sources(), ConflictPolicy::LastWins).unwrap();
+
+        assert_eq!(combination.flags["FIntFps"], "120");
+        assert_eq!(combination.conflicts[0].resolved_by.as_deref(), Some("preset:b"));
+    }
+
+    #[test]
+    fn prefer_preset_beats_later_custom_flags() {
+        let sources = [
+            source("preset:a", FlagSourceKind::Preset, &[("FIntFps", "144")]),
+            source("custom", FlagSourceKind::Custom, &[("FIntFps", "240")]),
+        ];
+        let combination = combine_flag_sources(&sources, ConflictPolicy::PreferPreset).unwrap();
+
+        assert_eq!(combination.flags["FIntFps"], "144");
+        assert_eq!(combination.conflicts[0].resolved_by.as_deref(), Some("preset:a"));
+    }
+
+    #[test]
+    fn prefer_custom_beats_later_presets() {
+        let combination = combine_flag_sources(&sources(), ConflictPolicy::PreferCustom).unwrap();
+
+        assert_eq!(combination.flags["FIntFps"], "240");
+        assert_eq!(combination.conflicts[0].resolved_by.as_deref(), Some("custom"));
+    }
+}
@@ -0,0 +1,304 @@
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::fs;
+use walkdir::WalkDir;
+use crate::error::DebeonError;
+use crate::models::RobloxInstallation;
+use crate::settings::PathSettings;
+
+#[cfg(target_os = "windows")]
+use winreg::enums::*;
+#[cfg(target_os = "windows")]
+use winreg::RegKey;
+
+/// Which installations an operation applies to.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum InstallTarget {
+    /// The most recently modified installation.
+    #[default]
+    Primary,
+    /// The installation with this `RobloxInstallation::id`.
+    Id(String),
+    All,
+}
+
+impl InstallTarget {
+    /// Reads an `installation` parameter: an ID, `all`, or nothing for the
+    /// primary installation.
+    pub fn from_param(value: Option<&str>) -> Self {
+        match value.map(str::trim) {
+            None | Some("") => InstallTarget::Primary,
+            Some(value) if value.eq_ignore_ascii_case("all") => InstallTarget::All,
+            Some(value) => InstallTarget::Id(value.to_string()),
+        }
+    }
+}
+
+/// Finds Roblox installations under the default and configured search paths.
+pub struct RobloxDetector {
+    search_paths: Vec<PathBuf>,
+}
+
+impl RobloxDetector {
+    pub fn new(paths: &PathSettings) -> Result<Self> {
+        let mut search_paths = Vec::new();
+
+        if let Some(local_app_data) = dirs::data_local_dir() {
+            search_paths.push(local_app_data.join("Roblox"));
+        }
+
+        if let Some(app_data) = dirs::data_dir() {
+            search_paths.push(app_data.join("Roblox"));
+        }
+
+        search_paths.push(PathBuf::from("C:\\Program Files (x86)\\Roblox"));
+        search_paths.push(PathBuf::from("C:\\Program Files\\Roblox"));
+
+        #[cfg(target_os = "windows")]
+        {
+            if let Ok(reg_path) = Self::get_registry_path() {
+                search_paths.push(reg_path);
+            }
+        }
+
+        search_paths.extend(paths.extra_search_paths.iter().cloned());
+        search_paths.retain(|path| {
+            !paths
+                .excluded_search_paths
+                .iter()
+                .any(|excluded| path.starts_with(excluded))
+        });
+        search_paths.dedup();
+
+        Ok(Self { search_paths })
+    }
+
+    #[cfg(target_os = "windows")]
+    fn get_registry_path() -> Result<PathBuf> {
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let roblox_key = hkcu.open_subkey("Software\\Roblox\\RobloxStudioBrowser\\roblox.com")?;
+        let path: String = roblox_key.get_value("InstallLocation")?;
+        Ok(PathBuf::from(path))
+    }
+
+    pub fn find_installations(&self) -> Result<Vec<RobloxInstallation>> {
+        let mut installations = Vec::new();
+
+        for base_path in &self.search_paths {
+            if !base_path.exists() {
+                continue;
+            }
+
+            for entry in WalkDir::new(base_path)
+                .max_depth(4)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+            {
+                let path = entry.path();
+
+                if path.file_name().and_then(|n| n.to_str()) == Some("RobloxPlayerBeta.exe")
+                    || path.file_name().and_then(|n| n.to_str()) == Some("RobloxStudioBeta.exe") {
+
+                    if let Some(version_dir) = path.parent() {
+                        let version = version_dir
+                            .file_name()
+                            .and_then(|n| n.to_str())
+                            .unwrap_or("unknown")
+                            .to_string();
+
+                        let metadata = fs::metadata(path)?;
+                        let modified = metadata.modified()?;
+                        let datetime: chrono::DateTime<chrono::Local> = modified.into();
+                        let channel = Self::detect_channel(path);
+
+                        installations.push(RobloxInstallation {
+                            id: Self::installation_id(&channel, version_dir),
+                            path: version_dir.to_string_lossy().to_string(),
+                            version,
+                            channel,
+                            last_modified: datetime.format("%Y-%m-%d %H:%M:%S").to_string(),
+                            primary: false,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self::newest_per_id(installations))
+    }
+
+    /// Overlapping search paths, or an old version directory Roblox has not
+    /// cleaned up yet, would otherwise list an installation twice under the
+    /// same ID. Sorted newest first, so the current version is the one kept
+    /// and the first installation is the primary one.
+    fn newest_per_id(mut installations: Vec<RobloxInstallation>) -> Vec<RobloxInstallation> {
+        installations.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+        let mut seen = std::collections::HashSet::new();
+        installations.retain(|i| seen.insert(i.id.clone()));
+
+        if let Some(primary) = installations.first_mut() {
+            primary.primary = true;
+        }
+
+        installations
+    }
+
+    pub fn get_primary_installation(&self) -> Result<PathBuf> {
+        self.find_installations()?
+            .into_iter()
+            .find(|i| i.primary)
+            .map(|i| PathBuf::from(i.path))
+            .ok_or_else(|| DebeonError::NoInstallation.into())
+    }
+
+    /// Installations selected by `target`. An unknown ID is `NotFound`; no
+    /// installations at all is `NoInstallation`.
+    pub fn resolve(&self, target: &InstallTarget) -> Result<Vec<RobloxInstallation>> {
+        let installations = self.find_installations()?;
+
+        if installations.is_empty() {
+            anyhow::bail!(DebeonError::NoInstallation);
+        }
+
+        let selected: Vec<RobloxInstallation> = match target {
+            InstallTarget::Primary => installations.into_iter().filter(|i| i.primary).collect(),
+            InstallTarget::Id(id) => installations.into_iter().filter(|i| &i.id == id).collect(),
+            InstallTarget::All => installations,
+        };
+
+        if let (InstallTarget::Id(id), true) = (target, selected.is_empty()) {
+            anyhow::bail!(DebeonError::NotFound(format!("Installation not found: {}", id)));
+        }
+
+        Ok(selected)
+    }
+
+    /// Channel plus a short hash of the install root, the directory holding
+    /// `Versions`. Roblox updates replace the `version-<hash>` directory, so
+    /// hashing the root keeps the ID across updates as well as restarts.
+    /// Windows paths are case-insensitive, so the root is lowercased there.
+    fn installation_id(channel: &str, version_dir: &Path) -> String {
+        let root = Self::install_root(version_dir).to_string_lossy();
+        #[cfg(windows)]
+        let root = root.to_lowercase();
+        let hash = hex::encode(Sha256::digest(root.as_bytes()));
+        format!("{}-{}", channel.to_lowercase(), &hash[..10])
+    }
+
+    /// Parent of the `Versions` directory, or the version directory itself
+    /// for installs that are not laid out that way.
+    fn install_root(version_dir: &Path) -> &Path {
+        version_dir
+            .parent()
+            .filter(|parent| {
+                parent
+                    .file_name()
+                    .is_some_and(|name| name.to_string_lossy().eq_ignore_ascii_case("versions"))
+            })
+            .and_then(Path::parent)
+            .unwrap_or(version_dir)
+    }
+
+    fn detect_channel(path: &Path) -> String {
+        let path_str = path.to_string_lossy().to_lowercase();
+
+        if path_str.contains("studio") {
+            "Studio".to_string()
+        } else if path_str.contains("player") {
+            "Player".to_string()
+        } else {
+            "Unknown".to_string()
+        }
+    }
+
+    pub fn get_client_settings_path(&self) -> Result<PathBuf> {
+        let install_path = self.get_primary_installation()?;
+        Ok(Self::client_settings_dir(&install_path))
+    }
+
+    pub fn client_settings_dir(install_path: &Path) -> PathBuf {
+        install_path.join("ClientSettings")
+    }
+
+    pub fn client_settings_file(install_path: &Path) -> PathBuf {
+        Self::client_settings_dir(install_path).join("ClientAppSettings.json")
+    }
+
+    pub fn ensure_client_settings_dir(install_path: &Path) -> Result<PathBuf> {
+        let settings_path = Self::client_settings_dir(install_path);
+
+        if !settings_path.exists() {
+            fs::create_dir_all(&settings_path)?;
+        }
+
+        Ok(settings_path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installation(id: &str, path: &str, last_modified: &str) -> RobloxInstallation {
+        RobloxInstallation {
+            id: id.to_string(),
+            path: path.to_string(),
+            version: String::new(),
+            channel: "Player".to_string(),
+            last_modified: last_modified.to_string(),
+            primary: false,
+        }
+    }
+
+    #[test]
+    fn installation_id_survives_version_updates() {
+        let old = Path::new("/roblox/Versions/version-aaaa");
+        let new = Path::new("/roblox/Versions/version-bbbb");
+
+        let id = RobloxDetector::installation_id("Player", old);
+
+        assert_eq!(id, RobloxDetector::installation_id("Player", new));
+        assert!(id.starts_with("player-"));
+    }
+
+    #[test]
+    fn installation_id_differs_by_root_and_channel() {
+        let version = Path::new("/roblox/Versions/version-aaaa");
+        let other_root = Path::new("/other/Versions/version-aaaa");
+
+        let id = RobloxDetector::installation_id("Player", version);
+
+        assert_ne!(id, RobloxDetector::installation_id("Player", other_root));
+        assert_ne!(id, RobloxDetector::installation_id("Studio", version));
+    }
+
+    #[test]
+    fn install_root_falls_back_to_the_version_directory() {
+        let versioned = Path::new("/roblox/versions/v1");
+        let flat = Path::new("/sober/v1");
+
+        assert_eq!(RobloxDetector::install_root(versioned), Path::new("/roblox"));
+        assert_eq!(RobloxDetector::install_root(flat), flat);
+    }
+
+    #[test]
+    fn newest_per_id_keeps_the_newest_directory_and_marks_it_primary() {
+        let installations = RobloxDetector::newest_per_id(vec![
+            installation("player-a", "/a/old", "2026-01-01 00:00:00"),
+            installation("studio-b", "/b", "2026-02-01 00:00:00"),
+            installation("player-a", "/a/new", "2026-03-01 00:00:00"),
+        ]);
+
+        let paths: Vec<_> = installations.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(paths, ["/a/new", "/b"]);
+        assert!(installations[0].primary);
+        assert!(!installations[1].primary);
+    }
+
+    #[test]
+    fn newest_per_id_accepts_no_installations() {
+        assert!(RobloxDetector::newest_per_id(Vec::new()).is_empty());
+    }
+}
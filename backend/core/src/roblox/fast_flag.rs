@@ -0,0 +1,227 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use utoipa::ToSchema;
+use crate::error::DebeonError;
+use crate::models::FlagDiff;
+
+/// Value type of a fast flag, determined by its name prefix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagKind {
+    Bool,
+    Int,
+    String,
+    LogLevel,
+}
+
+impl FlagKind {
+    /// Infers the kind from a flag name such as `FFlagDebugDisplayFPS` or
+    /// `DFIntTaskSchedulerTargetFps`. Returns `None` for unknown prefixes.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let unprefixed = name.strip_prefix('D').unwrap_or(name);
+
+        let (kind, rest) = if let Some(rest) = unprefixed.strip_prefix("FFlag") {
+            (FlagKind::Bool, rest)
+        } else if let Some(rest) = unprefixed.strip_prefix("FInt") {
+            (FlagKind::Int, rest)
+        } else if let Some(rest) = unprefixed.strip_prefix("FString") {
+            (FlagKind::String, rest)
+        } else if let Some(rest) = unprefixed.strip_prefix("FLog") {
+            (FlagKind::LogLevel, rest)
+        } else {
+            return None;
+        };
+
+        if rest.is_empty() {
+            return None;
+        }
+
+        Some(kind)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FlagKind::Bool => "bool",
+            FlagKind::Int => "int",
+            FlagKind::String => "string",
+            FlagKind::LogLevel => "log_level",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FlagValue {
+    Bool(bool),
+    Int(i64),
+    String(String),
+    LogLevel(u32),
+}
+
+impl FlagValue {
+    pub fn kind(&self) -> FlagKind {
+        match self {
+            FlagValue::Bool(_) => FlagKind::Bool,
+            FlagValue::Int(_) => FlagKind::Int,
+            FlagValue::String(_) => FlagKind::String,
+            FlagValue::LogLevel(_) => FlagKind::LogLevel,
+        }
+    }
+
+    pub fn to_json(&self) -> Value {
+        match self {
+            FlagValue::Bool(b) => Value::Bool(*b),
+            FlagValue::Int(i) => Value::Number((*i).into()),
+            FlagValue::String(s) => Value::String(s.clone()),
+            FlagValue::LogLevel(level) => Value::Number((*level).into()),
+        }
+    }
+}
+
+impl fmt::Display for FlagValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FlagValue::Bool(b) => write!(f, "{}", b),
+            FlagValue::Int(i) => write!(f, "{}", i),
+            FlagValue::String(s) => write!(f, "{}", s),
+            FlagValue::LogLevel(level) => write!(f, "{}", level),
+        }
+    }
+}
+
+/// A single validated fast flag. The value always matches the kind implied by
+/// the name prefix, so it serializes to the JSON type Roblox expects.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FastFlag {
+    name: String,
+    value: FlagValue,
+}
+
+impl FastFlag {
+    /// Builds a flag from a typed value, rejecting values whose type does not
+    /// match the name prefix.
+    pub fn new(name: impl Into<String>, value: FlagValue) -> Result<Self> {
+        let name = name.into();
+        let kind = Self::kind_of(&name)?;
+
+        if value.kind() != kind {
+            anyhow::bail!(DebeonError::Validation(format!(
+                "Flag {} expects a {} value, got {}",
+                name,
+                kind.as_str(),
+                value.kind().as_str()
+            )));
+        }
+
+        Ok(Self { name, value })
+    }
+
+    /// Parses a raw string value according to the kind implied by the name.
+    pub fn parse(name: &str, raw: &str) -> Result<Self> {
+        let kind = Self::kind_of(name)?;
+        let trimmed = raw.trim();
+
+        let value = match kind {
+            FlagKind::Bool => match trimmed.to_ascii_lowercase().as_str() {
+                "true" | "1" => Some(FlagValue::Bool(true)),
+                "false" | "0" => Some(FlagValue::Bool(false)),
+                _ => None,
+            },
+            FlagKind::Int => trimmed.parse::<i64>().ok().map(FlagValue::Int),
+            FlagKind::LogLevel => trimmed.parse::<u32>().ok().map(FlagValue::LogLevel),
+            FlagKind::String => Some(FlagValue::String(raw.to_string())),
+        };
+
+        match value {
+            Some(value) => Ok(Self { name: name.to_string(), value }),
+            None => anyhow::bail!(DebeonError::Validation(format!(
+                "Invalid {} value for {}: {:?}",
+                kind.as_str(),
+                name,
+                raw
+            ))),
+        }
+    }
+
+    /// Parses every entry of a name → raw value map, failing on the first
+    /// invalid flag so nothing is written half-applied.
+    pub fn parse_all<'a, I>(flags: I) -> Result<Vec<Self>>
+    where
+        I: IntoIterator<Item = (&'a String, &'a String)>,
+    {
+        flags
+            .into_iter()
+            .map(|(name, raw)| Self::parse(name, raw))
+            .collect()
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> &FlagValue {
+        &self.value
+    }
+
+    pub fn kind(&self) -> FlagKind {
+        self.value.kind()
+    }
+
+    pub fn to_json(&self) -> Value {
+        self.value.to_json()
+    }
+
+    fn kind_of(name: &str) -> Result<FlagKind> {
+        FlagKind::from_name(name).ok_or_else(|| {
+            DebeonError::Validation(format!(
+                "Unrecognized fast flag name: {} (expected an FFlag, FInt, FString or FLog prefix, optionally with D)",
+                name
+            ))
+            .into()
+        })
+    }
+}
+
+/// `current` with `flags` set on top of it.
+pub(crate) fn with_flags(current: Value, flags: &[FastFlag]) -> Result<Value> {
+    let mut updated = current;
+    let settings_obj = updated.as_object_mut().context("Invalid settings format")?;
+
+    for flag in flags {
+        settings_obj.insert(flag.name().to_string(), flag.to_json());
+    }
+
+    Ok(updated)
+}
+
+/// Returns the current contents of `settings_file` and the result of applying
+/// `flags` on top of it. A missing or unparseable file counts as empty, which
+/// matches what the writers replace it with once it has been backed up.
+pub(crate) fn stage_flags(settings_file: &Path, flags: &[FastFlag]) -> Result<(Value, Value)> {
+    let current = if settings_file.exists() {
+        let content = fs::read_to_string(settings_file)?;
+        serde_json::from_str::<Value>(&content)
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| serde_json::json!({}))
+    } else {
+        serde_json::json!({})
+    };
+
+    let updated = with_flags(current.clone(), flags)?;
+    Ok((current, updated))
+}
+
+/// Diff between the current settings file and what writing `flags` would produce.
+pub(crate) fn preview_flags(settings_file: &Path, flags: &[FastFlag]) -> Result<FlagDiff> {
+    let (current, updated) = stage_flags(settings_file, flags)?;
+    let empty = serde_json::Map::new();
+
+    Ok(FlagDiff::between(
+        current.as_object().unwrap_or(&empty),
+        updated.as_object().unwrap_or(&empty),
+    ))
+}
@@ -0,0 +1,290 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::Value;
+use super::backups::BackupStore;
+use super::detector::RobloxDetector;
+use super::catalog::FlagCatalog;
+use super::fast_flag::{preview_flags, stage_flags, with_flags, FastFlag};
+use super::ownership::{split_owned, OwnedSplit};
+use super::settings_file::SettingsFile;
+use crate::error::DebeonError;
+use crate::models::{FlagDiff, FlagPreset, FlagWarning};
+
+/// Reads and writes raw fast flags in an installation's
+/// `ClientAppSettings.json`. `install` is always the installation directory.
+pub struct FlagManager {
+    backups: BackupStore,
+}
+
+impl FlagManager {
+    pub fn new() -> Result<Self> {
+        Ok(Self { backups: BackupStore::new()? })
+    }
+
+    /// Writes `flags`, returning catalog warnings for unknown names and
+    /// out-of-range values. Warnings never block the write.
+    pub fn apply_flags(
+        &self,
+        install: &Path,
+        flags: &HashMap<String, String>,
+    ) -> Result<Vec<FlagWarning>> {
+        self.apply_flags_to(&SettingsFile::lock(install)?, flags)
+    }
+
+    pub(crate) fn apply_flags_to(
+        &self,
+        settings: &SettingsFile,
+        flags: &HashMap<String, String>,
+    ) -> Result<Vec<FlagWarning>> {
+        let parsed = FastFlag::parse_all(flags)?;
+        self.write_flags(settings, &parsed)?;
+
+        Ok(FlagCatalog::check(&parsed))
+    }
+
+    /// Writes already-parsed flags in order, so later entries win.
+    pub(crate) fn write_flags(&self, settings: &SettingsFile, flags: &[FastFlag]) -> Result<()> {
+        let current = settings.read_or_back_up(&self.backups)?;
+        settings.write(&with_flags(current, flags)?)
+    }
+
+    /// What `apply_flags` would change, without touching the file.
+    pub fn preview_flags(&self, install: &Path, flags: &HashMap<String, String>) -> Result<FlagDiff> {
+        let parsed = FastFlag::parse_all(flags)?;
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        let mut diff = preview_flags(&settings_file, &parsed)?;
+        diff.warnings = FlagCatalog::check(&parsed);
+        Ok(diff)
+    }
+
+    /// Drops the owned keys that still hold the value Debeon wrote, then
+    /// writes `flags`. Hand-edited and unowned keys are kept.
+    pub(crate) fn replace_owned(
+        &self,
+        settings: &SettingsFile,
+        owned: &BTreeMap<String, Value>,
+        flags: &[FastFlag],
+    ) -> Result<OwnedSplit> {
+        let current = settings.read_or_back_up(&self.backups)?;
+        let (updated, split) = without_owned(current, owned, flags)?;
+
+        if !split.removed.is_empty() || !flags.is_empty() {
+            settings.write(&updated)?;
+        }
+
+        Ok(split)
+    }
+
+    /// What `replace_owned` would change, without touching the file.
+    pub(crate) fn preview_replace_owned(
+        &self,
+        install: &Path,
+        owned: &BTreeMap<String, Value>,
+        flags: &[FastFlag],
+    ) -> Result<(FlagDiff, OwnedSplit)> {
+        let settings_file = RobloxDetector::client_settings_file(install);
+        let (current, _) = stage_flags(&settings_file, &[])?;
+        let (updated, split) = without_owned(current.clone(), owned, flags)?;
+
+        let empty = serde_json::Map::new();
+        let diff = FlagDiff::between(
+            current.as_object().unwrap_or(&empty),
+            updated.as_object().unwrap_or(&empty),
+        );
+        Ok((diff, split))
+    }
+
+    pub fn remove_flags(&self, install: &Path, keys: &[String]) -> Result<Vec<String>> {
+        self.remove_matching(install, &FlagSelector::Keys(keys.to_vec()))
+    }
+
+    /// Keys in `ClientAppSettings.json` that the selector would remove.
+    pub fn matching_keys(&self, install: &Path, selector: &FlagSelector) -> Result<Vec<String>> {
+        let Some(settings) = self.read_settings(install)? else {
+            return Ok(Vec::new());
+        };
+
+        let mut keys: Vec<String> = settings
+            .as_object()
+            .map(|obj| obj.keys().filter(|key| selector.matches(key)).cloned().collect())
+            .unwrap_or_default();
+        keys.sort();
+
+        Ok(keys)
+    }
+
+    pub fn remove_matching(&self, install: &Path, selector: &FlagSelector) -> Result<Vec<String>> {
+        self.remove_matching_from(&SettingsFile::lock(install)?, selector)
+    }
+
+    pub(crate) fn remove_matching_from(
+        &self,
+        settings_file: &SettingsFile,
+        selector: &FlagSelector,
+    ) -> Result<Vec<String>> {
+        let Some(mut settings) = settings_file.read()? else {
+            return Ok(Vec::new());
+        };
+
+        let mut removed = Vec::new();
+
+        if let Some(settings_obj) = settings.as_object_mut() {
+            settings_obj.retain(|key, _| {
+                let matched = selector.matches(key);
+                if matched {
+                    removed.push(key.clone());
+                }
+                !matched
+            });
+        }
+
+        if !removed.is_empty() {
+            settings_file.write(&settings)?;
+        }
+
+        removed.sort();
+        Ok(removed)
+    }
+
+    fn read_settings(&self, install: &Path) -> Result<Option<Value>> {
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        if !settings_file.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&settings_file)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    pub fn read_flags(&self, install: &Path) -> Result<HashMap<String, String>> {
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        if !settings_file.exists() {
+            return Ok(HashMap::new());
+        }
+
+        let content = fs::read_to_string(&settings_file)?;
+        let settings: Value = serde_json::from_str(&content)?;
+
+        let mut flags = HashMap::new();
+
+        if let Some(obj) = settings.as_object() {
+            for (key, value) in obj {
+                let value_str = match value {
+                    Value::String(s) => s.clone(),
+                    Value::Number(n) => n.to_string(),
+                    Value::Bool(b) => b.to_string(),
+                    _ => continue,
+                };
+                flags.insert(key.clone(), value_str);
+            }
+        }
+
+        Ok(flags)
+    }
+
+    /// Built-in presets grouped by category.
+    pub fn get_common_flags() -> HashMap<String, Vec<FlagPreset>> {
+        let mut categories: HashMap<String, Vec<FlagPreset>> = HashMap::new();
+
+        for preset in Self::builtin_presets() {
+            categories.entry(preset.category.clone()).or_default().push(preset);
+        }
+
+        categories
+    }
+
+    /// Built-in presets, loaded from the bundled `presets.json`.
+    pub fn builtin_presets() -> Vec<FlagPreset> {
+        BUILTIN_PRESETS.clone()
+    }
+}
+
+fn without_owned(
+    current: Value,
+    owned: &BTreeMap<String, Value>,
+    flags: &[FastFlag],
+) -> Result<(Value, OwnedSplit)> {
+    let rewritten: HashSet<&str> = flags.iter().map(FastFlag::name).collect();
+    let mut updated = current;
+    let settings_obj = updated.as_object_mut().context("Invalid settings format")?;
+
+    let split = split_owned(settings_obj, owned, &rewritten);
+    for key in &split.removed {
+        settings_obj.remove(key);
+    }
+
+    Ok((with_flags(updated, flags)?, split))
+}
+
+static BUILTIN_PRESETS: Lazy<Vec<FlagPreset>> = Lazy::new(|| {
+    let mut presets: Vec<FlagPreset> = serde_json::from_str(include_str!("presets.json"))
+        .expect("bundled presets.json is valid");
+
+    for preset in &mut presets {
+        preset.builtin = true;
+    }
+
+    presets
+});
+
+/// Which flags a remove operation targets.
+#[derive(Debug, Clone)]
+pub enum FlagSelector {
+    Keys(Vec<String>),
+    /// Unanchored regular expression matched against flag names.
+    Pattern(Regex),
+    All,
+}
+
+impl FlagSelector {
+    pub fn pattern(pattern: &str) -> Result<Self> {
+        Regex::new(pattern)
+            .map(FlagSelector::Pattern)
+            .map_err(|e| DebeonError::Validation(format!("Invalid flag pattern: {}", e)).into())
+    }
+
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            FlagSelector::Keys(keys) => keys.iter().any(|k| k == key),
+            FlagSelector::Pattern(regex) => regex.is_match(key),
+            FlagSelector::All => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_match_exact_names_only() {
+        let selector = FlagSelector::Keys(vec!["FFlagA".to_string()]);
+
+        assert!(selector.matches("FFlagA"));
+        assert!(!selector.matches("FFlagAB"));
+    }
+
+    #[test]
+    fn patterns_are_unanchored() {
+        let selector = FlagSelector::pattern("^DFInt|Fps").unwrap();
+
+        assert!(selector.matches("DFIntTaskSchedulerTargetFps"));
+        assert!(selector.matches("FIntTargetFps"));
+        assert!(!selector.matches("FFlagDebugGraphicsPreferVulkan"));
+        assert!(FlagSelector::All.matches("anything"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_validation_errors() {
+        let error = FlagSelector::pattern("(").unwrap_err();
+
+        assert!(matches!(error.downcast_ref::<DebeonError>(), Some(DebeonError::Validation(_))));
+    }
+}
@@ -0,0 +1,182 @@
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use crate::models::{FlagImport, ImportFormat, SkippedEntry};
+use super::catalog::FlagCatalog;
+use super::fast_flag::FastFlag;
+
+/// Parses flag lists exported by other bootstrappers or pasted from forums
+/// into typed, validated flags.
+pub struct FlagImporter;
+
+impl FlagImporter {
+    /// Detects the format of `content` and extracts every usable flag.
+    /// Entries that cannot be parsed or typed are reported in `skipped`
+    /// rather than failing the whole import.
+    pub fn parse(content: &str) -> FlagImport {
+        let content = content.trim_start_matches('\u{feff}');
+
+        match serde_json::from_str::<Value>(content) {
+            Ok(Value::Object(obj)) => match obj.get("fflags") {
+                Some(Value::Object(fflags)) => Self::from_object(ImportFormat::Sober, fflags),
+                _ => Self::from_object(ImportFormat::Json, &obj),
+            },
+            _ => Self::from_lines(content),
+        }
+    }
+
+    fn from_object(format: ImportFormat, obj: &Map<String, Value>) -> FlagImport {
+        let mut builder = ImportBuilder::default();
+
+        for (key, value) in obj {
+            let raw = match value {
+                Value::String(s) => s.clone(),
+                Value::Bool(b) => b.to_string(),
+                Value::Number(n) => n.to_string(),
+                _ => {
+                    builder.skip(None, format!("\"{}\": {}", key, value), "Value must be a string, number or boolean");
+                    continue;
+                }
+            };
+
+            builder.add(None, key, &raw, &format!("\"{}\": {}", key, value));
+        }
+
+        builder.finish(format)
+    }
+
+    fn from_lines(content: &str) -> FlagImport {
+        let mut builder = ImportBuilder::default();
+
+        for (index, line) in content.lines().enumerate() {
+            let line_number = Some(index + 1);
+            let trimmed = line.trim().trim_end_matches(',').trim();
+
+            if trimmed.is_empty()
+                || trimmed.starts_with('#')
+                || trimmed.starts_with("//")
+                || trimmed.starts_with(';')
+                || trimmed == "{"
+                || trimmed == "}"
+            {
+                continue;
+            }
+
+            // Split on whichever separator comes first so values may contain
+            // the other one, e.g. URLs in FString flags.
+            let Some(separator) = trimmed.find(&['=', ':'][..]) else {
+                builder.skip(line_number, line.to_string(), "Expected KEY=VALUE or \"KEY\": VALUE");
+                continue;
+            };
+
+            let (key, value) = (&trimmed[..separator], &trimmed[separator + 1..]);
+            builder.add(line_number, unquote(key), unquote(value), line);
+        }
+
+        builder.finish(ImportFormat::KeyValue)
+    }
+}
+
+#[derive(Default)]
+struct ImportBuilder {
+    flags: Vec<FastFlag>,
+    skipped: Vec<SkippedEntry>,
+}
+
+impl ImportBuilder {
+    fn add(&mut self, line: Option<usize>, key: &str, raw: &str, content: &str) {
+        match FastFlag::parse(key, raw) {
+            Ok(flag) => {
+                self.flags.retain(|existing| existing.name() != flag.name());
+                self.flags.push(flag);
+            }
+            Err(e) => self.skip(line, content.to_string(), &e.to_string()),
+        }
+    }
+
+    fn skip(&mut self, line: Option<usize>, content: String, reason: &str) {
+        self.skipped.push(SkippedEntry {
+            line,
+            content,
+            reason: reason.to_string(),
+        });
+    }
+
+    fn finish(self, format: ImportFormat) -> FlagImport {
+        let warnings = FlagCatalog::check(&self.flags);
+        let flags: BTreeMap<String, String> = self
+            .flags
+            .into_iter()
+            .map(|flag| (flag.name().to_string(), flag.value().to_string()))
+            .collect();
+
+        FlagImport {
+            format,
+            flags,
+            skipped: self.skipped,
+            warnings,
+        }
+    }
+}
+
+fn unquote(s: &str) -> &str {
+    let s = s.trim();
+    s.strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .or_else(|| s.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')))
+        .unwrap_or(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bloxstrap_json() {
+        let content = r#"{"FFlagDebugGraphicsPreferVulkan": "True", "DFIntTaskSchedulerTargetFps": 144}"#;
+        let import = FlagImporter::parse(content);
+
+        assert_eq!(import.format, ImportFormat::Json);
+        assert_eq!(import.flags["FFlagDebugGraphicsPreferVulkan"], "true");
+        assert_eq!(import.flags["DFIntTaskSchedulerTargetFps"], "144");
+        assert!(import.skipped.is_empty());
+    }
+
+    #[test]
+    fn parses_sober_config() {
+        let import = FlagImporter::parse(r#"{"use_opengl": false, "fflags": {"FIntDebugForceMSAASamples": 4}}"#);
+
+        assert_eq!(import.format, ImportFormat::Sober);
+        assert_eq!(import.flags.len(), 1);
+        assert_eq!(import.flags["FIntDebugForceMSAASamples"], "4");
+    }
+
+    #[test]
+    fn parses_key_value_lines() {
+        let content = "# comment\n\
+            FFlagHandleAltEnterFullscreenManually=False\n\
+            \"FStringDebugLuaLogPattern\": \"http://a\",\n\
+            not a flag\n";
+        let import = FlagImporter::parse(content);
+
+        assert_eq!(import.format, ImportFormat::KeyValue);
+        assert_eq!(import.flags["FFlagHandleAltEnterFullscreenManually"], "false");
+        assert_eq!(import.flags["FStringDebugLuaLogPattern"], "http://a");
+        assert_eq!(import.skipped.len(), 1);
+        assert_eq!(import.skipped[0].line, Some(4));
+    }
+
+    #[test]
+    fn skips_values_of_the_wrong_type() {
+        let import = FlagImporter::parse(r#"{"FIntFoo": "fast", "FFlagBar": [1]}"#);
+
+        assert!(import.flags.is_empty());
+        assert_eq!(import.skipped.len(), 2);
+    }
+
+    #[test]
+    fn later_duplicates_win() {
+        let import = FlagImporter::parse("FIntFoo=1\nFIntFoo=2\n");
+
+        assert_eq!(import.flags["FIntFoo"], "2");
+    }
+}
@@ -0,0 +1,84 @@
+use anyhow::Result;
+use reqwest::Client;
+use std::path::Path;
+use std::time::Duration;
+use crate::error::DebeonError;
+use crate::settings::ApiSettings;
+
+pub struct RobloxInstaller {
+    client: Client,
+    download_url: String,
+    client_settings_url: String,
+    request_timeout: Duration,
+    download_timeout: Duration,
+}
+
+impl RobloxInstaller {
+    pub fn new(client: Client, settings: &ApiSettings) -> Result<Self> {
+        Ok(Self {
+            client,
+            download_url: settings.setup_url.trim_end_matches('/').to_string(),
+            client_settings_url: settings.client_settings_url.trim_end_matches('/').to_string(),
+            request_timeout: Duration::from_secs(settings.request_timeout_secs),
+            download_timeout: Duration::from_secs(settings.download_timeout_secs),
+        })
+    }
+
+    pub async fn get_latest_version_info(&self) -> Result<VersionInfo> {
+        let url = format!("{}/client-version/WindowsPlayer", self.client_settings_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(self.request_timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to fetch version info"));
+        }
+
+        let response = response.json::<serde_json::Value>().await?;
+
+        Ok(VersionInfo {
+            version: response["clientVersionUpload"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+            deployment_url: format!("{}/", self.download_url),
+        })
+    }
+
+    pub async fn download_bootstrapper(&self, destination: &Path) -> Result<()> {
+        let url = format!("{}/RobloxPlayerLauncher.exe", self.download_url);
+        let response = self
+            .client
+            .get(&url)
+            .timeout(self.download_timeout)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            anyhow::bail!(DebeonError::upstream(response.status(), "Failed to download bootstrapper"));
+        }
+
+        let bytes = response.bytes().await?;
+        tokio::fs::write(destination, bytes).await?;
+
+        Ok(())
+    }
+
+    pub fn verify_installation(&self, path: &Path) -> Result<bool> {
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        let player_exe = path.join("RobloxPlayerBeta.exe");
+        Ok(player_exe.exists())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct VersionInfo {
+    pub version: String,
+    pub deployment_url: String,
+}
@@ -0,0 +1,373 @@
+use anyhow::Result;
+use crate::error::DebeonError;
+use crate::models::{ConfigCoverage, MappedField, RobloxConfig, UnbackedField};
+use super::fast_flag::{FastFlag, FlagKind, FlagValue};
+use super::user_settings::SettingValue;
+
+const NO_FLAG: &str = "No fast flag or user setting controls this";
+const NO_USER_SETTINGS: &str = "Roblox's user settings file was not found; start Roblox once to create it";
+/// Range of Roblox's mouse and gamepad camera sensitivity sliders.
+const MIN_SENSITIVITY: f32 = 0.2;
+const MAX_SENSITIVITY: f32 = 4.0;
+
+/// One flag written for a config field. `value` returns `None` when the field
+/// is unset, in which case the flag is left alone.
+struct FlagMapping {
+    key: &'static str,
+    kind: FlagKind,
+    value: fn(&RobloxConfig) -> Option<FlagValue>,
+}
+
+/// One `UserGameSettings` property written for a config field. The value's
+/// variant is the property's type.
+struct PropertyMapping {
+    name: &'static str,
+    value: fn(&RobloxConfig) -> Option<SettingValue>,
+}
+
+/// How one `RobloxConfig` field, named by its dotted path, reaches
+/// `ClientAppSettings.json` and Roblox's user settings XML.
+struct FieldMapping {
+    field: &'static str,
+    flags: &'static [FlagMapping],
+    properties: &'static [PropertyMapping],
+    /// Why nothing backs the field; reported back to the caller.
+    unbacked: Option<&'static str>,
+}
+
+const fn flags(field: &'static str, flags: &'static [FlagMapping]) -> FieldMapping {
+    FieldMapping { field, flags, properties: &[], unbacked: None }
+}
+
+const fn properties(field: &'static str, properties: &'static [PropertyMapping]) -> FieldMapping {
+    FieldMapping { field, flags: &[], properties, unbacked: None }
+}
+
+const fn unbacked(field: &'static str, reason: &'static str) -> FieldMapping {
+    FieldMapping { field, flags: &[], properties: &[], unbacked: Some(reason) }
+}
+
+/// Every typed `RobloxConfig` field, in declaration order. A field added to the
+/// config needs a row here, even if only to say that nothing backs it.
+static FIELD_MAPPINGS: &[FieldMapping] = &[
+    FieldMapping {
+        field: "graphics.graphics_quality",
+        flags: &[FlagMapping {
+            key: "DFIntDebugFRMQualityLevelOverride",
+            kind: FlagKind::Int,
+            value: |c| Some(FlagValue::Int(c.graphics.graphics_quality.into())),
+        }],
+        // The in-game slider has 10 steps over the 21 render quality levels;
+        // 0 is "Automatic".
+        properties: &[PropertyMapping {
+            name: "SavedQualityLevel",
+            value: |c| {
+                let level = match c.graphics.graphics_quality {
+                    0 => 0,
+                    quality => u32::from(quality).div_ceil(2).min(10),
+                };
+                Some(SettingValue::Token(level))
+            },
+        }],
+        unbacked: None,
+    },
+    unbacked("graphics.render_distance", "Roblox derives view distance from the graphics quality level"),
+    flags(
+        "graphics.shadow_quality",
+        &[FlagMapping {
+            key: "FIntRenderShadowIntensity",
+            kind: FlagKind::Int,
+            value: |c| Some(FlagValue::Int((i64::from(c.graphics.shadow_quality) * 25).min(100))),
+        }],
+    ),
+    flags(
+        "graphics.texture_quality",
+        &[
+            FlagMapping {
+                key: "DFIntTextureQualityOverride",
+                kind: FlagKind::Int,
+                value: |c| Some(FlagValue::Int(c.graphics.texture_quality.min(3).into())),
+            },
+            FlagMapping {
+                key: "DFFlagTextureQualityOverrideEnabled",
+                kind: FlagKind::Bool,
+                value: |_| Some(FlagValue::Bool(true)),
+            },
+        ],
+    ),
+    unbacked("graphics.particle_quality", NO_FLAG),
+    flags(
+        "graphics.vsync",
+        &[FlagMapping {
+            key: "FFlagEnableVSync",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.graphics.vsync)),
+        }],
+    ),
+    properties(
+        "graphics.fullscreen",
+        &[PropertyMapping {
+            name: "Fullscreen",
+            value: |c| Some(SettingValue::Bool(c.graphics.fullscreen)),
+        }],
+    ),
+    unbacked("graphics.resolution_width", "Roblox uses the window or desktop resolution"),
+    unbacked("graphics.resolution_height", "Roblox uses the window or desktop resolution"),
+    flags(
+        "graphics.anti_aliasing",
+        &[
+            FlagMapping {
+                key: "FFlagEnableAntiAliasing",
+                kind: FlagKind::Bool,
+                value: |c| Some(FlagValue::Bool(c.graphics.anti_aliasing > 0)),
+            },
+            FlagMapping {
+                key: "FIntDebugForceMSAASamples",
+                kind: FlagKind::Int,
+                value: |c| Some(FlagValue::Int(c.graphics.anti_aliasing.min(8).into())),
+            },
+        ],
+    ),
+    unbacked("graphics.anisotropic_filtering", NO_FLAG),
+    properties(
+        "audio.master_volume",
+        &[PropertyMapping {
+            name: "MasterVolume",
+            value: |c| Some(SettingValue::Float(c.audio.master_volume.clamp(0.0, 1.0))),
+        }],
+    ),
+    unbacked("audio.music_volume", "Roblox has a single master volume"),
+    unbacked("audio.sfx_volume", "Roblox has a single master volume"),
+    unbacked("audio.voice_volume", "Roblox has a single master volume"),
+    unbacked("audio.spatial_audio", NO_FLAG),
+    unbacked("audio.output_device", "Audio devices are chosen in Roblox's in-game settings"),
+    unbacked("audio.input_device", "Audio devices are chosen in Roblox's in-game settings"),
+    properties(
+        "controls.mouse_sensitivity",
+        &[PropertyMapping {
+            name: "MouseSensitivity",
+            value: |c| {
+                let sensitivity = c.controls.mouse_sensitivity.clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+                Some(SettingValue::Float(sensitivity))
+            },
+        }],
+    ),
+    properties(
+        "controls.invert_y_axis",
+        &[PropertyMapping {
+            name: "CameraYInverted",
+            value: |c| Some(SettingValue::Bool(c.controls.invert_y_axis)),
+        }],
+    ),
+    properties(
+        "controls.camera_mode",
+        &[PropertyMapping {
+            name: "ComputerCameraMovementMode",
+            value: |c| camera_mode(&c.controls.camera_mode).map(SettingValue::Token),
+        }],
+    ),
+    unbacked("controls.key_bindings", "Key bindings are defined by each experience"),
+    unbacked("controls.gamepad_enabled", NO_FLAG),
+    properties(
+        "controls.gamepad_sensitivity",
+        &[PropertyMapping {
+            name: "GamepadCameraSensitivity",
+            value: |c| {
+                let sensitivity = c.controls.gamepad_sensitivity.clamp(MIN_SENSITIVITY, MAX_SENSITIVITY);
+                Some(SettingValue::Float(sensitivity))
+            },
+        }],
+    ),
+    unbacked("network.preferred_region", "Roblox picks the server region when joining"),
+    unbacked("network.max_ping", NO_FLAG),
+    unbacked("network.connection_quality", NO_FLAG),
+    unbacked("network.enable_ipv6", NO_FLAG),
+    unbacked("network.data_usage_limit", NO_FLAG),
+    flags(
+        "rendering.frame_rate_limit",
+        &[FlagMapping {
+            key: "DFIntTaskSchedulerTargetFps",
+            kind: FlagKind::Int,
+            value: |c| c.rendering.frame_rate_limit.map(|fps| FlagValue::Int(fps.into())),
+        }],
+    ),
+    flags(
+        "rendering.dynamic_lighting",
+        &[FlagMapping {
+            key: "FFlagEnableDynamicLighting",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.dynamic_lighting)),
+        }],
+    ),
+    flags(
+        "rendering.post_processing",
+        &[FlagMapping {
+            key: "FFlagEnablePostProcessing",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.post_processing)),
+        }],
+    ),
+    flags(
+        "rendering.bloom",
+        &[FlagMapping {
+            key: "FFlagEnableBloom",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.bloom)),
+        }],
+    ),
+    flags(
+        "rendering.depth_of_field",
+        &[FlagMapping {
+            key: "FFlagEnableDepthOfField",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.depth_of_field)),
+        }],
+    ),
+    flags(
+        "rendering.motion_blur",
+        &[FlagMapping {
+            key: "FFlagEnableMotionBlur",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.motion_blur)),
+        }],
+    ),
+    flags(
+        "rendering.ambient_occlusion",
+        &[FlagMapping {
+            key: "FFlagEnableAmbientOcclusion",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.ambient_occlusion)),
+        }],
+    ),
+    flags(
+        "rendering.reflections",
+        &[FlagMapping {
+            key: "FFlagEnableReflections",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.rendering.reflections)),
+        }],
+    ),
+    unbacked("rendering.global_illumination", NO_FLAG),
+    flags(
+        "performance.low_latency_mode",
+        &[FlagMapping {
+            key: "FFlagEnableLowLatencyMode",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.performance.low_latency_mode)),
+        }],
+    ),
+    unbacked("performance.power_saving_mode", NO_FLAG),
+    unbacked("performance.background_performance", NO_FLAG),
+    unbacked("performance.memory_limit_mb", NO_FLAG),
+    unbacked("performance.cpu_affinity", "CPU affinity is a property of the process, not a fast flag"),
+    unbacked("performance.gpu_preference", "The GPU is chosen in the operating system's graphics settings"),
+    unbacked("ui.ui_scale", NO_FLAG),
+    flags(
+        "ui.show_fps",
+        &[FlagMapping {
+            key: "FFlagDebugDisplayFPS",
+            kind: FlagKind::Bool,
+            value: |c| Some(FlagValue::Bool(c.ui.show_fps)),
+        }],
+    ),
+    unbacked("ui.show_ping", NO_FLAG),
+    properties(
+        "ui.chat_enabled",
+        &[PropertyMapping {
+            name: "ChatVisible",
+            value: |c| Some(SettingValue::Bool(c.ui.chat_enabled)),
+        }],
+    ),
+    unbacked(
+        "ui.gui_transparency",
+        "Roblox's background transparency setting has no matching scale",
+    ),
+    unbacked("ui.theme", NO_FLAG),
+    unbacked("ui.custom_cursor", NO_FLAG),
+];
+
+fn camera_mode(mode: &str) -> Option<u32> {
+    match mode.to_ascii_lowercase().replace(['-', ' '], "_").as_str() {
+        "default" => Some(0),
+        "classic" => Some(1),
+        "follow" => Some(2),
+        "orbital" => Some(3),
+        "camera_toggle" => Some(4),
+        _ => None,
+    }
+}
+
+/// Flags and user settings for the typed fields of a config, not counting its
+/// custom flags, plus which fields they cover.
+pub(crate) struct MappedConfig {
+    pub(crate) flags: Vec<FastFlag>,
+    pub(crate) properties: Vec<(&'static str, SettingValue)>,
+    pub(crate) coverage: ConfigCoverage,
+}
+
+/// Maps every field of `config`. Without a user settings file, fields backed
+/// only by user settings are reported as unbacked.
+pub(crate) fn map_config(config: &RobloxConfig, user_settings: bool) -> Result<MappedConfig> {
+    let mut mapped = MappedConfig {
+        flags: Vec::new(),
+        properties: Vec::new(),
+        coverage: ConfigCoverage::default(),
+    };
+
+    for mapping in FIELD_MAPPINGS {
+        if let Some(reason) = mapping.unbacked {
+            mapped.coverage.unbacked.push(UnbackedField {
+                field: mapping.field.to_string(),
+                reason: reason.to_string(),
+            });
+            continue;
+        }
+
+        let mut keys = Vec::new();
+        for flag_mapping in mapping.flags {
+            let Some(value) = (flag_mapping.value)(config) else {
+                continue;
+            };
+
+            if value.kind() != flag_mapping.kind {
+                anyhow::bail!(DebeonError::Internal(format!(
+                    "Mapping for {} produced a {} value for {}",
+                    mapping.field,
+                    value.kind().as_str(),
+                    flag_mapping.key
+                )));
+            }
+
+            mapped.flags.push(FastFlag::new(flag_mapping.key, value)?);
+            keys.push(flag_mapping.key.to_string());
+        }
+
+        let mut names = Vec::new();
+        if user_settings {
+            for property in mapping.properties {
+                if let Some(value) = (property.value)(config) {
+                    mapped.properties.push((property.name, value));
+                    names.push(property.name.to_string());
+                }
+            }
+        } else if mapping.flags.is_empty() {
+            mapped.coverage.unbacked.push(UnbackedField {
+                field: mapping.field.to_string(),
+                reason: NO_USER_SETTINGS.to_string(),
+            });
+            continue;
+        }
+
+        if keys.is_empty() && names.is_empty() {
+            mapped.coverage.unset.push(mapping.field.to_string());
+        } else {
+            mapped.coverage.honored.push(MappedField {
+                field: mapping.field.to_string(),
+                flags: keys,
+                properties: names,
+            });
+        }
+    }
+
+    Ok(mapped)
+}
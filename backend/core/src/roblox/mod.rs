@@ -0,0 +1,599 @@
+mod applied;
+mod backups;
+mod detector;
+mod installer;
+mod patcher;
+mod flags;
+mod catalog;
+mod importer;
+mod mapping;
+mod conflicts;
+mod fast_flag;
+mod ownership;
+mod settings_file;
+mod upstream;
+mod user_settings;
+
+pub use applied::AppliedStore;
+pub use backups::BackupStore;
+pub use detector::{InstallTarget, RobloxDetector};
+pub use installer::RobloxInstaller;
+pub use patcher::ConfigPatcher;
+pub use flags::{FlagManager, FlagSelector};
+pub use catalog::FlagCatalog;
+pub use importer::FlagImporter;
+pub use conflicts::{combine_flag_sources, find_conflicts, FlagSource, FlagSourceKind};
+pub use fast_flag::{FastFlag, FlagKind, FlagValue};
+pub use ownership::OwnershipStore;
+pub use upstream::UpstreamFlags;
+pub use user_settings::{SettingValue, UserSettingsFile};
+pub(crate) use settings_file::content_hash;
+
+use anyhow::Result;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::sync::RwLock;
+use crate::audit::AuditLog;
+use crate::error::DebeonError;
+use crate::models::{
+    AuditEntry, AuditOperation, AuditSource, BackupInfo, ConfigApplied, FlagDiff, FlagOwnership,
+    FlagRemoval, FlagsApplied, OwnedRemoval, ReapplyReason, ReapplyReport, ReapplyStatus,
+    RobloxConfig, RobloxInstallation, UserSettings, UserSettingsPreview, UserSettingsWrite,
+};
+use crate::settings::DebeonSettings;
+use settings_file::SettingsFile;
+
+/// Facade over detection, config patching, fast flags and backups. Operations
+/// take an `InstallTarget`; writes accept `InstallTarget::All`, while reads and
+/// previews need a single installation. Every write to `ClientAppSettings.json`
+/// made through it is recorded in the audit log and in the ownership manifest,
+/// and the resulting profile and flags are remembered per channel so they can
+/// be re-applied after updates.
+pub struct RobloxManager {
+    detector: RobloxDetector,
+    installer: RobloxInstaller,
+    patcher: ConfigPatcher,
+    flag_manager: FlagManager,
+    backups: BackupStore,
+    applied: AppliedStore,
+    ownership: OwnershipStore,
+    audit: AuditLog,
+    /// Hash of the settings file each installation was left with by Debeon's
+    /// last write, keyed by installation ID.
+    written: Mutex<HashMap<String, Option<String>>>,
+}
+
+impl RobloxManager {
+    pub fn new(settings: &DebeonSettings, client: reqwest::Client) -> Result<Self> {
+        Ok(Self {
+            detector: RobloxDetector::new(&settings.paths)?,
+            installer: RobloxInstaller::new(client, &settings.api)?,
+            patcher: ConfigPatcher::new()?,
+            flag_manager: FlagManager::new()?,
+            backups: BackupStore::new()?,
+            applied: AppliedStore::new()?,
+            ownership: OwnershipStore::new()?,
+            audit: AuditLog::new()?,
+            written: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Runs synchronous, filesystem-heavy manager work on tokio's blocking pool
+    /// so installation scans and settings writes never stall async workers.
+    pub async fn run_blocking<T, F>(manager: &Arc<RwLock<Self>>, f: F) -> Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Self) -> Result<T> + Send + 'static,
+    {
+        let manager = manager.clone();
+        tokio::task::spawn_blocking(move || f(&manager.blocking_read())).await?
+    }
+
+    pub fn installer(&self) -> &RobloxInstaller {
+        &self.installer
+    }
+
+    pub fn audit_log(&self) -> &AuditLog {
+        &self.audit
+    }
+
+    pub fn find_installations(&self) -> Result<Vec<RobloxInstallation>> {
+        self.detector.find_installations()
+    }
+
+    pub fn resolve_installations(&self, target: &InstallTarget) -> Result<Vec<RobloxInstallation>> {
+        self.detector.resolve(target)
+    }
+
+    pub fn get_install_path(&self) -> Result<PathBuf> {
+        self.detector.get_primary_installation()
+    }
+
+    /// `ClientAppSettings.json` of the primary installation.
+    pub fn get_client_settings_file(&self) -> Result<PathBuf> {
+        Ok(self.detector.get_client_settings_path()?.join("ClientAppSettings.json"))
+    }
+
+    /// Applies `config` to every targeted installation and to their user
+    /// settings files, reporting the installation IDs and which config fields
+    /// were written.
+    pub fn apply_config(
+        &self,
+        target: &InstallTarget,
+        config: &RobloxConfig,
+        source: AuditSource,
+    ) -> Result<ConfigApplied> {
+        ConfigPatcher::config_flags(config)?;
+        let profile = profile_name(&source);
+        let targets = self.resolve_installations(target)?;
+        let user_settings = self.apply_user_settings(&targets, config)?;
+        let coverage = ConfigPatcher::coverage(config, !user_settings.is_empty())?;
+        let mut installations = Vec::new();
+
+        for installation in targets {
+            self.audited(&installation, AuditOperation::ApplyConfig, source.clone(), |settings| {
+                self.patcher.apply_configuration_to(settings, config)?;
+                self.remember(self.applied.record_config(&installation.channel, profile.clone(), config));
+                Ok(())
+            })?;
+            installations.push(installation.id);
+        }
+
+        Ok(ConfigApplied { installations, coverage, user_settings })
+    }
+
+    /// The `UserGameSettings` properties of the installation's user settings
+    /// file.
+    pub fn user_settings(&self, target: &InstallTarget) -> Result<UserSettings> {
+        let installation = self.single_target(target)?;
+        let file = UserSettingsFile::locate(&installation).ok_or_else(|| {
+            DebeonError::NotFound(format!("No Roblox user settings file found for {}", installation.id))
+        })?;
+
+        Ok(UserSettings {
+            path: file.path().to_string_lossy().to_string(),
+            properties: file.read()?,
+        })
+    }
+
+    /// What `apply_config` would change in `ClientAppSettings.json` and the
+    /// user settings XML, without touching either.
+    pub fn preview_config(&self, target: &InstallTarget, config: &RobloxConfig) -> Result<FlagDiff> {
+        let installation = self.single_target(target)?;
+        let mut diff = self.patcher.preview_configuration(Path::new(&installation.path), config)?;
+        diff.user_settings = self.preview_user_settings(&installation, config)?;
+        Ok(diff)
+    }
+
+    pub fn backup_config(&self, target: &InstallTarget) -> Result<Option<BackupInfo>> {
+        self.backups.create(&self.single_installation(target)?)
+    }
+
+    /// Copies a backup over the settings of every targeted installation,
+    /// returning their IDs.
+    pub fn restore_config(&self, target: &InstallTarget, backup_name: &str) -> Result<Vec<String>> {
+        let source = AuditSource::Restore { backup: backup_name.to_string() };
+        let mut restored = Vec::new();
+
+        for installation in self.resolve_installations(target)? {
+            self.audited(&installation, AuditOperation::RestoreBackup, source.clone(), |settings| {
+                self.backups.restore_to(settings, backup_name)?;
+                self.remember(self.applied.forget(&installation.channel));
+                Ok(())
+            })?;
+            restored.push(installation.id);
+        }
+
+        Ok(restored)
+    }
+
+    /// Backups, newest first. With a specific target, only backups taken from
+    /// that installation.
+    pub fn list_backups(&self, target: &InstallTarget) -> Result<Vec<BackupInfo>> {
+        let backups = self.backups.list()?;
+
+        if *target == InstallTarget::All {
+            return Ok(backups);
+        }
+
+        let install = self.single_installation(target)?.to_string_lossy().to_string();
+        Ok(backups
+            .into_iter()
+            .filter(|b| b.source_installation.as_deref() == Some(install.as_str()))
+            .collect())
+    }
+
+    pub fn diff_backup(&self, target: &InstallTarget, backup_name: &str) -> Result<FlagDiff> {
+        self.backups.diff(&self.single_installation(target)?, backup_name)
+    }
+
+    /// Writes `flags` to every targeted installation. Catalog warnings depend
+    /// only on the flags, so they are computed once, and the flags are parsed
+    /// before anything is written. The writes are not transactional: if one
+    /// installation fails, those before it keep the flags, and each of them is
+    /// recorded in the audit log.
+    pub fn set_fast_flags(
+        &self,
+        target: &InstallTarget,
+        flags: &HashMap<String, String>,
+        source: AuditSource,
+    ) -> Result<FlagsApplied> {
+        let parsed = FastFlag::parse_all(flags)?;
+        let warnings = FlagCatalog::check(&parsed);
+        let mut installations = Vec::new();
+
+        for installation in self.resolve_installations(target)? {
+            self.audited(&installation, AuditOperation::SetFlags, source.clone(), |settings| {
+                self.flag_manager.write_flags(settings, &parsed)?;
+                self.remember(self.applied.record_flags(&installation.channel, flags));
+                Ok(())
+            })?;
+            installations.push(installation.id);
+        }
+
+        let mut applied: Vec<String> = flags.keys().cloned().collect();
+        applied.sort();
+
+        Ok(FlagsApplied { applied, warnings, installations })
+    }
+
+    pub fn preview_fast_flags(
+        &self,
+        target: &InstallTarget,
+        flags: &HashMap<String, String>,
+    ) -> Result<FlagDiff> {
+        self.flag_manager.preview_flags(&self.single_installation(target)?, flags)
+    }
+
+    /// Removes the selected flags from every targeted installation, backing
+    /// up each `ClientAppSettings.json` first when anything is going to change.
+    pub fn remove_fast_flags(
+        &self,
+        target: &InstallTarget,
+        selector: &FlagSelector,
+        source: AuditSource,
+    ) -> Result<FlagRemoval> {
+        let mut removal = FlagRemoval { removed: Vec::new(), backups: Vec::new() };
+
+        for installation in self.resolve_installations(target)? {
+            let install = PathBuf::from(&installation.path);
+            // Held across the check, the backup and the write, so the backup is
+            // exactly what the removal starts from.
+            let settings = SettingsFile::lock(&install)?;
+
+            if self.flag_manager.matching_keys(&install, selector)?.is_empty() {
+                continue;
+            }
+
+            removal.backups.extend(self.backups.create(&install)?);
+            let operation = AuditOperation::RemoveFlags;
+            let source = source.clone();
+            let removed = self.audited_locked(&settings, &installation, operation, source, |settings| {
+                let removed = self.flag_manager.remove_matching_from(settings, selector)?;
+                self.remember(self.applied.forget_flags(&installation.channel, &removed));
+                Ok(removed)
+            })?;
+            removal.removed.extend(removed);
+        }
+
+        removal.removed.sort();
+        removal.removed.dedup();
+        Ok(removal)
+    }
+
+    pub fn get_fast_flags(&self, target: &InstallTarget) -> Result<HashMap<String, String>> {
+        self.flag_manager.read_flags(&self.single_installation(target)?)
+    }
+
+    /// Applies `config` in place of the previous profile: keys Debeon wrote
+    /// earlier that still hold its value and that `config` does not set are
+    /// removed first. Hand-edited and unowned keys are kept and reported.
+    pub fn switch_config(
+        &self,
+        target: &InstallTarget,
+        config: &RobloxConfig,
+        source: AuditSource,
+    ) -> Result<OwnedRemoval> {
+        let flags = ConfigPatcher::config_flags(config)?;
+        let profile = profile_name(&source);
+        let user_settings = self.apply_user_settings(&self.resolve_installations(target)?, config)?;
+
+        let mut removal =
+            self.replace_owned(target, &flags, AuditOperation::ApplyConfig, source, |installation| {
+                self.remember(self.applied.forget(&installation.channel));
+                self.remember(self.applied.record_config(&installation.channel, profile.clone(), config));
+            })?;
+        removal.coverage = Some(ConfigPatcher::coverage(config, !user_settings.is_empty())?);
+        removal.user_settings = user_settings;
+        Ok(removal)
+    }
+
+    /// What `switch_config` would change, without touching the file.
+    pub fn preview_switch(&self, target: &InstallTarget, config: &RobloxConfig) -> Result<FlagDiff> {
+        let installation = self.single_target(target)?;
+        let owned = self.ownership.owned(&installation.id)?;
+        let flags = ConfigPatcher::config_flags(config)?;
+
+        let (mut diff, _) = self.flag_manager.preview_replace_owned(
+            Path::new(&installation.path),
+            &owned,
+            &flags,
+        )?;
+        diff.user_settings = self.preview_user_settings(&installation, config)?;
+        Ok(diff)
+    }
+
+    /// Removes every key Debeon wrote that still holds the value it wrote, and
+    /// stops re-applying settings to the installations' channels.
+    pub fn uninstall_changes(&self, target: &InstallTarget) -> Result<OwnedRemoval> {
+        let operation = AuditOperation::RemoveOwned;
+        self.replace_owned(target, &[], operation, AuditSource::Manual, |installation| {
+            self.remember(self.applied.forget(&installation.channel));
+        })
+    }
+
+    /// Which keys of the installation's settings Debeon owns, without changing
+    /// anything.
+    pub fn flag_ownership(&self, target: &InstallTarget) -> Result<FlagOwnership> {
+        let installation = self.single_target(target)?;
+        let owned = self.ownership.owned(&installation.id)?;
+
+        let (_, split) = self.flag_manager.preview_replace_owned(
+            Path::new(&installation.path),
+            &owned,
+            &[],
+        )?;
+
+        Ok(FlagOwnership {
+            installation: installation.id,
+            owned: split.removed,
+            hand_edited: split.hand_edited,
+            unowned: split.unowned,
+        })
+    }
+
+    /// Writes the profile and flags remembered for the installation's channel
+    /// back into its `ClientAppSettings.json`, but only the keys that are
+    /// missing. Keys with a different value are hand edits and are kept,
+    /// except for new installations and manual re-applies, which also restore
+    /// them. Returns `None` when nothing is remembered or nothing has drifted.
+    /// Re-applies are audited and kept in the re-apply history.
+    pub fn reapply(
+        &self,
+        installation: &RobloxInstallation,
+        reason: ReapplyReason,
+    ) -> Result<Option<ReapplyReport>> {
+        // Held from reading the remembered state to the write, so a concurrent
+        // removal cannot be undone with stale state.
+        let settings = SettingsFile::lock(Path::new(&installation.path))?;
+        let Some(state) = self.applied.state(&installation.channel)? else {
+            return Ok(None);
+        };
+
+        let mut flags = match &state.config {
+            Some(config) => ConfigPatcher::config_flags(config)?,
+            None => Vec::new(),
+        };
+        flags.extend(FastFlag::parse_all(&state.flags)?);
+
+        let install = PathBuf::from(&installation.path);
+        let diff = fast_flag::preview_flags(&RobloxDetector::client_settings_file(&install), &flags)?;
+
+        let restore_changed = matches!(reason, ReapplyReason::NewInstallation | ReapplyReason::Manual);
+        let mut keys: Vec<String> = diff.added.into_keys().collect();
+        if restore_changed {
+            keys.extend(diff.changed.into_keys());
+        }
+        if keys.is_empty() {
+            return Ok(None);
+        }
+        keys.sort();
+        flags.retain(|flag| keys.binary_search_by(|key| key.as_str().cmp(flag.name())).is_ok());
+
+        let source = AuditSource::Reapply { reason };
+        self.audited_locked(&settings, installation, AuditOperation::Reapply, source, |settings| {
+            self.flag_manager.write_flags(settings, &flags)
+        })?;
+        drop(settings);
+
+        let report = ReapplyReport {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            installation: installation.id.clone(),
+            channel: installation.channel.clone(),
+            reason,
+            profile: state.profile,
+            keys,
+        };
+        self.remember(self.applied.push_report(report.clone()));
+
+        Ok(Some(report))
+    }
+
+    /// Remembered profile and flags per channel and recent re-applies.
+    pub fn reapply_status(&self) -> Result<ReapplyStatus> {
+        self.applied.status()
+    }
+
+    /// Whether `hash` is what Debeon's own last write left in the
+    /// installation's settings file, so the watcher can tell it from an
+    /// external rewrite even before the write's event arrives.
+    pub fn wrote_last(&self, installation_id: &str, hash: Option<&str>) -> bool {
+        let written = self.written.lock().unwrap_or_else(|e| e.into_inner());
+        written.get(installation_id).is_some_and(|last| last.as_deref() == hash)
+    }
+
+    /// Directory of the one installation `target` names. `All` is rejected,
+    /// since reads and previews cannot be merged across installations.
+    fn single_installation(&self, target: &InstallTarget) -> Result<PathBuf> {
+        Ok(PathBuf::from(self.single_target(target)?.path))
+    }
+
+    /// The one installation `target` names; fails for "all".
+    pub fn single_target(&self, target: &InstallTarget) -> Result<RobloxInstallation> {
+        if *target == InstallTarget::All {
+            anyhow::bail!(DebeonError::Validation(
+                "This operation needs a single installation, not \"all\"".to_string()
+            ));
+        }
+
+        self.resolve_installations(target)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| DebeonError::NoInstallation.into())
+    }
+
+    /// Drops the owned keys that still hold Debeon's value from every targeted
+    /// installation and writes `flags`, backing up first when anything is
+    /// removed. Hand-edited keys are released from the manifest.
+    /// `after_write` runs for each installation once its file is updated.
+    fn replace_owned(
+        &self,
+        target: &InstallTarget,
+        flags: &[FastFlag],
+        operation: AuditOperation,
+        source: AuditSource,
+        after_write: impl Fn(&RobloxInstallation),
+    ) -> Result<OwnedRemoval> {
+        let mut removal = OwnedRemoval::default();
+
+        for installation in self.resolve_installations(target)? {
+            let install = PathBuf::from(&installation.path);
+            let settings = SettingsFile::lock(&install)?;
+            let owned = self.ownership.owned(&installation.id)?;
+            let (_, planned) = self.flag_manager.preview_replace_owned(&install, &owned, flags)?;
+
+            let split = if planned.removed.is_empty() && flags.is_empty() {
+                planned
+            } else {
+                if !planned.removed.is_empty() {
+                    removal.backups.extend(self.backups.create(&install)?);
+                }
+                self.audited_locked(&settings, &installation, operation, source.clone(), |settings| {
+                    self.flag_manager.replace_owned(settings, &owned, flags)
+                })?
+            };
+
+            if let Err(e) = self.ownership.release(&installation, &split.hand_edited) {
+                eprintln!("Failed to update {}: {:#}", self.ownership.path().display(), e);
+            }
+            after_write(&installation);
+
+            removal.removed.extend(split.removed);
+            removal.hand_edited.extend(split.hand_edited);
+            removal.unowned.extend(split.unowned);
+            removal.installations.push(installation.id);
+        }
+
+        for keys in [&mut removal.removed, &mut removal.hand_edited, &mut removal.unowned] {
+            keys.sort();
+            keys.dedup();
+        }
+        Ok(removal)
+    }
+
+    /// Writes the user settings part of `config` once per distinct user
+    /// settings file; several installations usually share one. Installations
+    /// without a file are skipped.
+    fn apply_user_settings(
+        &self,
+        installations: &[RobloxInstallation],
+        config: &RobloxConfig,
+    ) -> Result<Vec<UserSettingsWrite>> {
+        let mut files: Vec<UserSettingsFile> = Vec::new();
+        for file in installations.iter().filter_map(UserSettingsFile::locate) {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+
+        files
+            .iter()
+            .map(|file| self.patcher.apply_user_settings(file, config))
+            .collect()
+    }
+
+    fn preview_user_settings(
+        &self,
+        installation: &RobloxInstallation,
+        config: &RobloxConfig,
+    ) -> Result<Vec<UserSettingsPreview>> {
+        UserSettingsFile::locate(installation)
+            .map(|file| self.patcher.preview_user_settings(&file, config))
+            .into_iter()
+            .collect()
+    }
+
+    /// Failing to update the remembered state is reported but, like the audit
+    /// log, never fails a write that already happened.
+    fn remember(&self, result: Result<()>) {
+        if let Err(e) = result {
+            eprintln!("Failed to update {}: {:#}", self.applied.path().display(), e);
+        }
+    }
+
+    /// Runs `write` against the installation's locked settings file, appends
+    /// the per-key changes it made to the audit log and updates the ownership
+    /// manifest. Both snapshots are taken under the same lock as the write, so
+    /// concurrent writers never leak into the diff. Config, flag and re-apply
+    /// writes claim the keys they set; other writes only release keys. A
+    /// failure to log is reported but does not undo or fail the write.
+    fn audited<T>(
+        &self,
+        installation: &RobloxInstallation,
+        operation: AuditOperation,
+        source: AuditSource,
+        write: impl FnOnce(&SettingsFile) -> Result<T>,
+    ) -> Result<T> {
+        let settings = SettingsFile::lock(Path::new(&installation.path))?;
+        self.audited_locked(&settings, installation, operation, source, write)
+    }
+
+    /// `audited` for a caller that already holds the installation's settings
+    /// lock.
+    fn audited_locked<T>(
+        &self,
+        settings: &SettingsFile,
+        installation: &RobloxInstallation,
+        operation: AuditOperation,
+        source: AuditSource,
+        write: impl FnOnce(&SettingsFile) -> Result<T>,
+    ) -> Result<T> {
+        let before = settings.object();
+        let result = write(settings)?;
+        let after = settings.object();
+        self.written
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(installation.id.clone(), settings.hash());
+
+        let entry = AuditEntry::new(
+            operation,
+            source,
+            installation.path.clone(),
+            &before,
+            &after,
+        );
+
+        let claim = matches!(
+            operation,
+            AuditOperation::ApplyConfig | AuditOperation::SetFlags | AuditOperation::Reapply
+        );
+        if let Err(e) = self.ownership.record(installation, &entry.changes, claim) {
+            eprintln!("Failed to update {}: {:#}", self.ownership.path().display(), e);
+        }
+
+        if let Err(e) = self.audit.record(&entry) {
+            eprintln!("Failed to record {} in audit log: {:#}", operation.as_str(), e);
+        }
+
+        Ok(result)
+    }
+}
+
+fn profile_name(source: &AuditSource) -> Option<String> {
+    match source {
+        AuditSource::Profile { name } => Some(name.clone()),
+        _ => None,
+    }
+}
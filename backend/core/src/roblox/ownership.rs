@@ -0,0 +1,152 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::models::{AuditChange, RobloxInstallation};
+use crate::storage::{write_atomic, FileLock};
+
+const OWNERSHIP_FILE: &str = "ownership.json";
+
+/// Per-installation manifest of the keys Debeon wrote to
+/// `ClientAppSettings.json` and the value it wrote to each. A key whose value
+/// no longer matches was changed by hand and is left alone.
+pub struct OwnershipStore {
+    path: PathBuf,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct OwnershipFile {
+    installations: BTreeMap<String, OwnedKeys>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct OwnedKeys {
+    /// Installation directory, kept so the file is readable on its own.
+    path: String,
+    keys: BTreeMap<String, Value>,
+}
+
+/// Keys of a settings file sorted by who owns them. `removed` holds the owned
+/// keys that still have Debeon's value and are about to be dropped.
+#[derive(Debug, Default)]
+pub(crate) struct OwnedSplit {
+    pub(crate) removed: Vec<String>,
+    pub(crate) hand_edited: Vec<String>,
+    pub(crate) unowned: Vec<String>,
+}
+
+impl OwnershipStore {
+    pub fn new() -> Result<Self> {
+        let data_dir = dirs::data_local_dir()
+            .context("Failed to get local data directory")?
+            .join("Debeon");
+
+        if !data_dir.exists() {
+            fs::create_dir_all(&data_dir)?;
+        }
+
+        Ok(Self { path: data_dir.join(OWNERSHIP_FILE) })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Keys Debeon wrote to the installation, with the values it wrote.
+    pub fn owned(&self, installation_id: &str) -> Result<BTreeMap<String, Value>> {
+        Ok(self
+            .load()?
+            .installations
+            .remove(installation_id)
+            .map(|owned| owned.keys)
+            .unwrap_or_default())
+    }
+
+    /// Updates the manifest from the per-key changes of one write. With
+    /// `claim`, every key the write set becomes Debeon's; otherwise keys only
+    /// stay Debeon's while they keep the value it wrote. Removed keys are
+    /// always released.
+    pub fn record(
+        &self,
+        installation: &RobloxInstallation,
+        changes: &BTreeMap<String, AuditChange>,
+        claim: bool,
+    ) -> Result<()> {
+        self.update(installation, |keys| {
+            for (key, change) in changes {
+                match &change.after {
+                    Some(value) if claim => {
+                        keys.insert(key.clone(), value.clone());
+                    }
+                    Some(value) if keys.get(key) == Some(value) => {}
+                    _ => {
+                        keys.remove(key);
+                    }
+                }
+            }
+        })
+    }
+
+    pub fn release(&self, installation: &RobloxInstallation, released: &[String]) -> Result<()> {
+        self.update(installation, |keys| {
+            keys.retain(|key, _| !released.contains(key));
+        })
+    }
+
+    fn update(
+        &self,
+        installation: &RobloxInstallation,
+        change: impl FnOnce(&mut BTreeMap<String, Value>),
+    ) -> Result<()> {
+        let _lock = FileLock::acquire(&self.path)?;
+        let mut file = self.load()?;
+
+        let owned = file.installations.entry(installation.id.clone()).or_default();
+        owned.path = installation.path.clone();
+        change(&mut owned.keys);
+
+        if owned.keys.is_empty() {
+            file.installations.remove(&installation.id);
+        }
+
+        write_atomic(&self.path, serde_json::to_string_pretty(&file)?.as_bytes())
+    }
+
+    fn load(&self) -> Result<OwnershipFile> {
+        if !self.path.exists() {
+            return Ok(OwnershipFile::default());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Invalid ownership manifest: {}", self.path.display()))
+    }
+}
+
+/// Sorts the keys of `current` against the manifest. Keys in `rewritten` are
+/// about to be written again, so they are neither removed nor reported.
+pub(crate) fn split_owned(
+    current: &Map<String, Value>,
+    owned: &BTreeMap<String, Value>,
+    rewritten: &HashSet<&str>,
+) -> OwnedSplit {
+    let mut split = OwnedSplit::default();
+
+    for (key, value) in current {
+        if rewritten.contains(key.as_str()) {
+            continue;
+        }
+
+        match owned.get(key) {
+            Some(owned_value) if owned_value == value => split.removed.push(key.clone()),
+            Some(_) => split.hand_edited.push(key.clone()),
+            None => split.unowned.push(key.clone()),
+        }
+    }
+
+    split
+}
@@ -0,0 +1,87 @@
+use anyhow::Result;
+use std::path::Path;
+use crate::models::{ConfigCoverage, FlagDiff, RobloxConfig, UserSettingsPreview, UserSettingsWrite};
+use super::backups::BackupStore;
+use super::detector::RobloxDetector;
+use super::fast_flag::{preview_flags, with_flags, FastFlag};
+use super::mapping::map_config;
+use super::settings_file::SettingsFile;
+use super::user_settings::UserSettingsFile;
+
+/// Translates a `RobloxConfig` into an installation's `ClientAppSettings.json`
+/// and Roblox's user settings XML. `install` is always the installation
+/// directory.
+pub struct ConfigPatcher {
+    backups: BackupStore,
+}
+
+impl ConfigPatcher {
+    pub fn new() -> Result<Self> {
+        Ok(Self { backups: BackupStore::new()? })
+    }
+
+    pub fn apply_configuration(&self, install: &Path, config: &RobloxConfig) -> Result<()> {
+        self.apply_configuration_to(&SettingsFile::lock(install)?, config)
+    }
+
+    pub(crate) fn apply_configuration_to(&self, settings: &SettingsFile, config: &RobloxConfig) -> Result<()> {
+        let flags = Self::config_flags(config)?;
+        let current = settings.read_or_back_up(&self.backups)?;
+        settings.write(&with_flags(current, &flags)?)
+    }
+
+    /// Writes the config fields that live in Roblox's user settings XML,
+    /// backing the file up first if anything changes.
+    pub fn apply_user_settings(&self, file: &UserSettingsFile, config: &RobloxConfig) -> Result<UserSettingsWrite> {
+        let properties = map_config(config, true)?.properties;
+        let mut backup = None;
+
+        let changed = file.write(&properties, |path| {
+            backup = Some(self.backups.create_user_settings(path)?);
+            Ok(())
+        })?;
+
+        Ok(UserSettingsWrite {
+            path: file.path().to_string_lossy().to_string(),
+            changed,
+            backup,
+        })
+    }
+
+    /// What `apply_user_settings` would change, without touching the file.
+    pub fn preview_user_settings(&self, file: &UserSettingsFile, config: &RobloxConfig) -> Result<UserSettingsPreview> {
+        let properties = map_config(config, true)?.properties;
+
+        Ok(UserSettingsPreview {
+            path: file.path().to_string_lossy().to_string(),
+            changes: file.preview(&properties)?,
+        })
+    }
+
+    /// What `apply_configuration` would change, without touching the file.
+    pub fn preview_configuration(&self, install: &Path, config: &RobloxConfig) -> Result<FlagDiff> {
+        let flags = Self::config_flags(config)?;
+        let settings_file = RobloxDetector::client_settings_file(install);
+
+        preview_flags(&settings_file, &flags)
+    }
+
+    pub(crate) fn config_flags(config: &RobloxConfig) -> Result<Vec<FastFlag>> {
+        let mut flags = Self::mapped_flags(config)?;
+        flags.extend(FastFlag::parse_all(&config.custom_flags)?);
+        Ok(flags)
+    }
+
+    /// Flags derived from the typed `RobloxConfig` fields, not counting its
+    /// custom flags.
+    pub fn mapped_flags(config: &RobloxConfig) -> Result<Vec<FastFlag>> {
+        Ok(map_config(config, false)?.flags)
+    }
+
+    /// Which typed fields of `config` are written as flags or user settings
+    /// and which have nothing behind them. `user_settings` says whether a user
+    /// settings file was found to write to.
+    pub fn coverage(config: &RobloxConfig, user_settings: bool) -> Result<ConfigCoverage> {
+        Ok(map_config(config, user_settings)?.coverage)
+    }
+}
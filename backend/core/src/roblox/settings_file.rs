@@ -0,0 +1,96 @@
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::storage::{write_atomic, FileLock};
+use super::backups::BackupStore;
+use super::detector::RobloxDetector;
+
+/// An installation's `ClientAppSettings.json`, locked against other Debeon
+/// writers for as long as this value lives. Every read-modify-write of the
+/// file goes through one of these.
+pub(crate) struct SettingsFile {
+    install: PathBuf,
+    path: PathBuf,
+    _lock: FileLock,
+}
+
+impl SettingsFile {
+    pub(crate) fn lock(install: &Path) -> Result<Self> {
+        let path = RobloxDetector::ensure_client_settings_dir(install)?.join("ClientAppSettings.json");
+        let lock = FileLock::acquire(&path)?;
+
+        Ok(Self {
+            install: install.to_path_buf(),
+            path,
+            _lock: lock,
+        })
+    }
+
+    /// Current contents, or `None` when the file does not exist. Fails if the
+    /// file is not valid JSON.
+    pub(crate) fn read(&self) -> Result<Option<Value>> {
+        if !self.path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        let value = serde_json::from_str(&content)
+            .with_context(|| format!("{} is not valid JSON", self.path.display()))?;
+        Ok(Some(value))
+    }
+
+    /// Current top-level object for auditing; missing or unparseable files
+    /// read as empty, matching how the writers treat them.
+    pub(crate) fn object(&self) -> Map<String, Value> {
+        match self.read() {
+            Ok(Some(Value::Object(obj))) => obj,
+            _ => Map::new(),
+        }
+    }
+
+    /// Current settings object, about to be overwritten. A file that is not a
+    /// JSON object is backed up first and then treated as empty; if the backup
+    /// fails, so does this.
+    pub(crate) fn read_or_back_up(&self, backups: &BackupStore) -> Result<Value> {
+        match self.read() {
+            Ok(None) => Ok(serde_json::json!({})),
+            Ok(Some(value)) if value.is_object() => Ok(value),
+            _ => {
+                let backup = backups
+                    .create(&self.install)
+                    .with_context(|| format!("Refusing to overwrite unreadable {}", self.path.display()))?;
+
+                if let Some(backup) = backup {
+                    eprintln!(
+                        "{} was not a valid settings object; saved a copy as backup {}",
+                        self.path.display(),
+                        backup.name
+                    );
+                }
+
+                Ok(serde_json::json!({}))
+            }
+        }
+    }
+
+    /// Hash of the current contents, as the settings watcher computes it, or
+    /// `None` when the file cannot be read.
+    pub(crate) fn hash(&self) -> Option<String> {
+        fs::read(&self.path).ok().map(|content| content_hash(&content))
+    }
+
+    pub(crate) fn write(&self, settings: &Value) -> Result<()> {
+        self.write_raw(serde_json::to_string_pretty(settings)?.as_bytes())
+    }
+
+    pub(crate) fn write_raw(&self, contents: &[u8]) -> Result<()> {
+        write_atomic(&self.path, contents)
+    }
+}
+
+/// Hex-encoded SHA-256 of a settings file's contents.
+pub(crate) fn content_hash(content: &[u8]) -> String {
+    hex::encode(Sha256::digest(content))
+}
@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use crate::api::RemoteClientSettings;
+use crate::models::{RemoteComparison, RemoteFlagStatus, RobloxInstallation};
+use super::fast_flag::FastFlag;
+
+/// Compares local fast flags with the client settings Roblox publishes.
+pub struct UpstreamFlags;
+
+impl UpstreamFlags {
+    /// Client settings application the installation reads its defaults from.
+    pub fn default_application(installation: &RobloxInstallation) -> &'static str {
+        if installation.channel.eq_ignore_ascii_case("studio") {
+            "PCStudioApp"
+        } else {
+            "PCDesktopClient"
+        }
+    }
+
+    pub fn compare(
+        installation: &str,
+        local: &HashMap<String, String>,
+        remote: &RemoteClientSettings,
+    ) -> RemoteComparison {
+        let mut flags: Vec<RemoteFlagStatus> = local
+            .iter()
+            .map(|(key, value)| {
+                let upstream = remote.settings.get(key).cloned();
+                let changes_default = upstream
+                    .as_deref()
+                    .map(|upstream| !same_value(key, value, upstream))
+                    .unwrap_or(false);
+
+                RemoteFlagStatus {
+                    key: key.clone(),
+                    local: value.clone(),
+                    exists_upstream: upstream.is_some(),
+                    upstream,
+                    changes_default,
+                }
+            })
+            .collect();
+        flags.sort_by(|a, b| a.key.cmp(&b.key));
+
+        RemoteComparison {
+            installation: installation.to_string(),
+            application: remote.application.clone(),
+            channel: remote.channel.clone(),
+            fetched: remote.fetched.clone(),
+            stale: remote.stale,
+            missing_upstream: flags.iter().filter(|f| !f.exists_upstream).count(),
+            redundant: flags.iter().filter(|f| f.exists_upstream && !f.changes_default).count(),
+            flags,
+        }
+    }
+}
+
+/// Compares typed values so `"True"` matches `"true"` and `"010"` matches
+/// `"10"`; falls back to a case-insensitive string match for names without a
+/// known prefix or values that do not parse.
+fn same_value(key: &str, local: &str, upstream: &str) -> bool {
+    match (FastFlag::parse(key, local), FastFlag::parse(key, upstream)) {
+        (Ok(local), Ok(upstream)) => local.value() == upstream.value(),
+        _ => local.trim().eq_ignore_ascii_case(upstream.trim()),
+    }
+}
@@ -0,0 +1,421 @@
+use anyhow::{Context, Result};
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::error::DebeonError;
+use crate::models::{RobloxInstallation, UserSetting, UserSettingChange};
+use crate::storage::{write_atomic, FileLock};
+
+/// Class of the `<Item>` holding the user's in-game settings.
+const SETTINGS_CLASS: &str = "UserGameSettings";
+const FLOAT_TOLERANCE: f32 = 1e-4;
+
+/// Typed value of a `UserGameSettings` property. The variant decides the
+/// element written for a property the file does not have yet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SettingValue {
+    Bool(bool),
+    Int(i64),
+    Float(f32),
+    /// Enum value, stored by its number.
+    Token(u32),
+}
+
+impl SettingValue {
+    fn tag(&self) -> &'static str {
+        match self {
+            SettingValue::Bool(_) => "bool",
+            SettingValue::Int(_) => "int",
+            SettingValue::Float(_) => "float",
+            SettingValue::Token(_) => "token",
+        }
+    }
+
+    fn text(&self) -> String {
+        match self {
+            SettingValue::Bool(b) => b.to_string(),
+            SettingValue::Int(i) => i.to_string(),
+            SettingValue::Float(f) => f.to_string(),
+            SettingValue::Token(t) => t.to_string(),
+        }
+    }
+
+    /// Whether the file's `text` already holds this value. Values are compared
+    /// parsed, floats within a small tolerance, since Roblox writes floats with
+    /// more digits than they were set with.
+    fn matches(&self, text: &str) -> bool {
+        let text = text.trim();
+        match self {
+            SettingValue::Bool(b) => text.eq_ignore_ascii_case(&b.to_string()),
+            SettingValue::Int(i) => text.parse::<i64>().is_ok_and(|v| v == *i),
+            SettingValue::Float(f) => text.parse::<f32>().is_ok_and(|v| (v - f).abs() <= FLOAT_TOLERANCE),
+            SettingValue::Token(t) => text.parse::<u32>().is_ok_and(|v| v == *t),
+        }
+    }
+}
+
+/// Roblox's per-user settings file, `GlobalBasicSettings_<n>.xml` for the
+/// player or `GlobalSettings_<n>.xml` for Studio. Volume, sensitivity, camera
+/// and window settings live here rather than in `ClientAppSettings.json`.
+/// Writes only touch the named properties; every other element, comment and
+/// whitespace run is copied through as is.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserSettingsFile {
+    path: PathBuf,
+}
+
+impl UserSettingsFile {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The settings file Roblox uses for `installation`: the highest numbered
+    /// one in the first directory above the installation that has any, or in
+    /// the `Roblox` folder of the local data dir. `None` until Roblox has been
+    /// started once and written it.
+    pub fn locate(installation: &RobloxInstallation) -> Option<Self> {
+        let prefix = if installation.channel.eq_ignore_ascii_case("studio") {
+            "GlobalSettings_"
+        } else {
+            "GlobalBasicSettings_"
+        };
+
+        let fallback = dirs::data_local_dir().map(|dir| dir.join("Roblox"));
+
+        Path::new(&installation.path)
+            .ancestors()
+            .skip(1)
+            .map(Path::to_path_buf)
+            .chain(fallback)
+            .find_map(|dir| newest_settings_file(&dir, prefix))
+            .map(Self::new)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every `UserGameSettings` property with its element type and raw text.
+    pub fn read(&self) -> Result<BTreeMap<String, UserSetting>> {
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        read_properties(&content)
+            .with_context(|| format!("{} is not a valid Roblox settings file", self.path.display()))
+    }
+
+    /// The properties of `properties` whose value differs from the file's.
+    pub(crate) fn preview(&self, properties: &[(&str, SettingValue)]) -> Result<Vec<UserSettingChange>> {
+        Ok(changes(&self.read()?, properties))
+    }
+
+    /// Sets `properties` and returns the names whose value changed. Nothing is
+    /// written when none did; otherwise `back_up` runs first and a failed
+    /// backup cancels the write.
+    pub(crate) fn write(
+        &self,
+        properties: &[(&str, SettingValue)],
+        back_up: impl FnOnce(&Path) -> Result<()>,
+    ) -> Result<Vec<String>> {
+        let _lock = FileLock::acquire(&self.path)?;
+
+        let content = fs::read_to_string(&self.path)
+            .with_context(|| format!("Failed to read {}", self.path.display()))?;
+        let current = read_properties(&content)
+            .with_context(|| format!("{} is not a valid Roblox settings file", self.path.display()))?;
+
+        let changed: Vec<String> = changes(&current, properties).into_iter().map(|c| c.name).collect();
+
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+
+        back_up(&self.path)?;
+        write_atomic(&self.path, set_properties(&content, properties)?.as_bytes())?;
+        Ok(changed)
+    }
+}
+
+fn changes(
+    current: &BTreeMap<String, UserSetting>,
+    properties: &[(&str, SettingValue)],
+) -> Vec<UserSettingChange> {
+    properties
+        .iter()
+        .filter_map(|(name, value)| {
+            let old = current.get(*name).map(|c| c.value.trim().to_string());
+            let unchanged = old.as_deref().is_some_and(|old| value.matches(old));
+            (!unchanged).then(|| UserSettingChange {
+                name: name.to_string(),
+                old,
+                new: value.text(),
+            })
+        })
+        .collect()
+}
+
+fn newest_settings_file(dir: &Path, prefix: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let number: u32 = name.strip_prefix(prefix)?.strip_suffix(".xml")?.parse().ok()?;
+            Some((number, entry.path()))
+        })
+        .max_by_key(|(number, _)| *number)
+        .map(|(_, path)| path)
+}
+
+/// Where the reader is relative to the `UserGameSettings` item, by element
+/// depth.
+#[derive(Default)]
+struct Position {
+    depth: usize,
+    item: Option<usize>,
+    properties: Option<usize>,
+    found: bool,
+}
+
+impl Position {
+    /// Tracks an opening tag; returns the property name if the element is a
+    /// direct child of the settings item's `<Properties>`.
+    fn start(&mut self, element: &BytesStart) -> Result<Option<String>> {
+        self.depth += 1;
+
+        let property = match self.properties {
+            Some(depth) if depth + 1 == self.depth => attribute(element, "name")?,
+            _ => None,
+        };
+
+        match element.name().as_ref() {
+            b"Item"
+                if self.item.is_none()
+                    && !self.found
+                    && attribute(element, "class")?.as_deref() == Some(SETTINGS_CLASS) =>
+            {
+                self.item = Some(self.depth);
+            }
+            b"Properties" if self.item.is_some_and(|depth| depth + 1 == self.depth) => {
+                self.properties = Some(self.depth);
+            }
+            _ => {}
+        }
+
+        Ok(property)
+    }
+
+    /// Tracks a closing tag; returns true when it closes `<Properties>`.
+    fn end(&mut self) -> bool {
+        let closes_properties = self.properties == Some(self.depth);
+
+        if closes_properties {
+            self.properties = None;
+            self.found = true;
+        }
+        if self.item == Some(self.depth) {
+            self.item = None;
+        }
+
+        self.depth = self.depth.saturating_sub(1);
+        closes_properties
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    Ok(match element.try_get_attribute(name)? {
+        Some(attribute) => Some(attribute.unescape_value()?.to_string()),
+        None => None,
+    })
+}
+
+fn not_settings_file() -> anyhow::Error {
+    DebeonError::Validation(format!("No {} properties found", SETTINGS_CLASS)).into()
+}
+
+fn read_properties(xml: &str) -> Result<BTreeMap<String, UserSetting>> {
+    let mut reader = Reader::from_str(xml);
+    let mut position = Position::default();
+    let mut properties = BTreeMap::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                if let Some(name) = position.start(&element)? {
+                    let kind = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                    let value = reader.read_text(element.name())?.trim().to_string();
+                    position.end();
+                    properties.insert(name, UserSetting { kind, value });
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(name) = position.start(&element)? {
+                    let kind = String::from_utf8_lossy(element.name().as_ref()).to_string();
+                    properties.insert(name, UserSetting { kind, value: String::new() });
+                }
+                position.end();
+            }
+            Event::End(_) => {
+                position.end();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    if !position.found {
+        return Err(not_settings_file());
+    }
+
+    Ok(properties)
+}
+
+/// Rewrites `xml` with `properties` set. Existing properties keep their
+/// element type and only have their text replaced; missing ones are appended
+/// to the end of `<Properties>`.
+fn set_properties(xml: &str, properties: &[(&str, SettingValue)]) -> Result<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut writer = Writer::new(Vec::new());
+    let mut position = Position::default();
+    let mut pending: BTreeMap<&str, &SettingValue> =
+        properties.iter().map(|(name, value)| (*name, value)).collect();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => match position.start(&element)? {
+                Some(name) if pending.contains_key(name.as_str()) => {
+                    let value = pending.remove(name.as_str()).expect("checked above");
+                    reader.read_to_end(element.name())?;
+                    position.end();
+                    writer.write_event(Event::Start(element.borrow()))?;
+                    writer.write_event(Event::Text(BytesText::new(&value.text())))?;
+                    writer.write_event(Event::End(element.to_end()))?;
+                }
+                _ => writer.write_event(Event::Start(element))?,
+            },
+            Event::Empty(element) => {
+                match position.start(&element)? {
+                    Some(name) if pending.contains_key(name.as_str()) => {
+                        let value = pending.remove(name.as_str()).expect("checked above");
+                        writer.write_event(Event::Start(element.borrow()))?;
+                        writer.write_event(Event::Text(BytesText::new(&value.text())))?;
+                        writer.write_event(Event::End(element.to_end()))?;
+                    }
+                    _ => writer.write_event(Event::Empty(element))?,
+                }
+                position.end();
+            }
+            Event::End(element) => {
+                if position.end() {
+                    for (name, value) in std::mem::take(&mut pending) {
+                        let mut start = BytesStart::new(value.tag());
+                        start.push_attribute(("name", name));
+                        writer.write_event(Event::Text(BytesText::from_escaped("\t")))?;
+                        writer.write_event(Event::Start(start.borrow()))?;
+                        writer.write_event(Event::Text(BytesText::new(&value.text())))?;
+                        writer.write_event(Event::End(start.to_end()))?;
+                        writer.write_event(Event::Text(BytesText::from_escaped("\n\t\t")))?;
+                    }
+                }
+                writer.write_event(Event::End(element))?;
+            }
+            Event::Eof => break,
+            event => writer.write_event(event)?,
+        }
+    }
+
+    if !position.found {
+        return Err(not_settings_file());
+    }
+
+    Ok(String::from_utf8(writer.into_inner())?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XML: &str = r#"<roblox version="4">
+	<Item class="UserGameSettings" referent="RBX0">
+		<Properties>
+			<!-- kept as is -->
+			<float name="MasterVolume">0.800000012</float>
+			<token name="SavedQualityLevel">3</token>
+			<Vector2 name="StartScreenPosition"><X>0</X><Y>0</Y></Vector2>
+		</Properties>
+	</Item>
+	<Item class="Other">
+		<Properties>
+			<float name="MouseSensitivity">9</float>
+		</Properties>
+	</Item>
+</roblox>"#;
+
+    #[test]
+    fn set_properties_with_nothing_to_set_keeps_the_file() {
+        assert_eq!(set_properties(XML, &[]).unwrap(), XML);
+    }
+
+    #[test]
+    fn set_properties_replaces_only_the_named_text() {
+        let xml = set_properties(XML, &[("SavedQualityLevel", SettingValue::Token(7))]).unwrap();
+
+        let expected = XML.replace(
+            r#"<token name="SavedQualityLevel">3</token>"#,
+            r#"<token name="SavedQualityLevel">7</token>"#,
+        );
+        assert_eq!(xml, expected);
+    }
+
+    #[test]
+    fn set_properties_appends_missing_properties_to_the_settings_item() {
+        let xml = set_properties(XML, &[("MouseSensitivity", SettingValue::Float(1.5))]).unwrap();
+        let properties = read_properties(&xml).unwrap();
+
+        assert_eq!(properties["MouseSensitivity"].kind, "float");
+        assert_eq!(properties["MouseSensitivity"].value, "1.5");
+        assert!(xml.contains(r#"<float name="MouseSensitivity">9</float>"#));
+        assert_eq!(properties["MasterVolume"].value, "0.800000012");
+    }
+
+    #[test]
+    fn read_properties_keeps_nested_values_as_xml() {
+        let properties = read_properties(XML).unwrap();
+
+        assert_eq!(properties.len(), 3);
+        assert_eq!(properties["StartScreenPosition"].kind, "Vector2");
+        assert_eq!(properties["StartScreenPosition"].value, "<X>0</X><Y>0</Y>");
+    }
+
+    #[test]
+    fn files_without_user_game_settings_are_rejected() {
+        let xml = r#"<roblox><Item class="Other"><Properties/></Item></roblox>"#;
+
+        assert!(read_properties(xml).is_err());
+        assert!(set_properties(xml, &[("MasterVolume", SettingValue::Float(1.0))]).is_err());
+    }
+
+    #[test]
+    fn changes_compare_parsed_values() {
+        let current = read_properties(XML).unwrap();
+        let properties = [
+            ("MasterVolume", SettingValue::Float(0.8)),
+            ("SavedQualityLevel", SettingValue::Token(3)),
+            ("MouseSensitivity", SettingValue::Float(1.0)),
+        ];
+
+        let changes = changes(&current, &properties);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "MouseSensitivity");
+        assert_eq!(changes[0].old, None);
+    }
+
+    #[test]
+    fn setting_values_match_case_insensitive_bools() {
+        assert!(SettingValue::Bool(true).matches("True"));
+        assert!(!SettingValue::Bool(false).matches("true"));
+        assert!(!SettingValue::Int(2).matches("two"));
+    }
+}
@@ -0,0 +1,249 @@
+use anyhow::{Result, Context};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+const SETTINGS_FILE: &str = "settings.toml";
+const ENV_PREFIX: &str = "DEBEON__";
+
+/// Backend settings, layered as: defaults, `settings.toml` in the config dir,
+/// `DEBEON__SECTION__KEY` environment variables, then command line arguments.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DebeonSettings {
+    pub server: ServerSettings,
+    pub api: ApiSettings,
+    pub cache: CacheSettings,
+    pub paths: PathSettings,
+    pub watcher: WatcherSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ServerSettings {
+    pub bind_address: String,
+    pub port: u16,
+    /// Origins allowed to make cross-origin requests; empty rejects all of them.
+    pub allowed_origins: Vec<String>,
+    /// Where the session token is written; defaults to `session.token` in the config dir.
+    pub token_file: Option<PathBuf>,
+}
+
+impl Default for ServerSettings {
+    fn default() -> Self {
+        Self {
+            bind_address: String::from("127.0.0.1"),
+            port: 8080,
+            allowed_origins: Vec::new(),
+            token_file: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiSettings {
+    pub users_url: String,
+    pub games_url: String,
+    pub economy_url: String,
+    pub asset_delivery_url: String,
+    pub thumbnails_url: String,
+    pub client_settings_url: String,
+    /// How long downloaded client settings are reused before fetching again.
+    pub client_settings_cache_secs: u64,
+    pub setup_url: String,
+    pub request_timeout_secs: u64,
+    pub download_timeout_secs: u64,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            users_url: String::from("https://users.roblox.com/v1"),
+            games_url: String::from("https://games.roblox.com/v1"),
+            economy_url: String::from("https://economy.roblox.com/v2"),
+            asset_delivery_url: String::from("https://assetdelivery.roblox.com/v1"),
+            thumbnails_url: String::from("https://thumbnails.roblox.com/v1"),
+            client_settings_url: String::from("https://clientsettingscdn.roblox.com/v2"),
+            client_settings_cache_secs: 3600,
+            setup_url: String::from("https://setup.rbxcdn.com"),
+            request_timeout_secs: 30,
+            download_timeout_secs: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheSettings {
+    /// Upper bound for the asset cache; `0` disables eviction.
+    pub max_size_mb: u64,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self { max_size_mb: 512 }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PathSettings {
+    pub extra_search_paths: Vec<PathBuf>,
+    pub excluded_search_paths: Vec<PathBuf>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatcherSettings {
+    /// Re-apply the last profile and flags when a Roblox update or another
+    /// program drops them from `ClientAppSettings.json`.
+    pub reapply: bool,
+}
+
+impl Default for WatcherSettings {
+    fn default() -> Self {
+        Self { reapply: true }
+    }
+}
+
+impl DebeonSettings {
+    pub fn load() -> Result<Self> {
+        let args: Vec<String> = std::env::args().skip(1).collect();
+        Self::load_from(std::env::vars(), &args)
+    }
+
+    pub fn load_from<I>(env: I, args: &[String]) -> Result<Self>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let env: Vec<(String, String)> = env.into_iter().collect();
+        let cli = CliOverrides::parse(args)?;
+
+        let settings_file = match cli.settings_file.clone().or_else(|| {
+            env.iter()
+                .find(|(key, _)| key == "DEBEON_SETTINGS")
+                .map(|(_, value)| PathBuf::from(value))
+        }) {
+            Some(path) => path,
+            None => Self::default_path()?,
+        };
+
+        let mut table = if settings_file.exists() {
+            let content = fs::read_to_string(&settings_file)?;
+            toml::from_str::<toml::Table>(&content)
+                .with_context(|| format!("Invalid settings file: {}", settings_file.display()))?
+        } else {
+            toml::Table::new()
+        };
+
+        for (key, value) in &env {
+            if let Some(path) = key.strip_prefix(ENV_PREFIX) {
+                let path = path.to_lowercase().replace("__", ".");
+                set_path(&mut table, &path, value)?;
+            }
+        }
+
+        for (path, value) in &cli.overrides {
+            set_path(&mut table, path, value)?;
+        }
+
+        let settings: Self = toml::Value::Table(table)
+            .try_into()
+            .context("Invalid settings")?;
+
+        Ok(settings)
+    }
+
+    pub fn default_path() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join(SETTINGS_FILE))
+    }
+
+    pub fn config_dir() -> Result<PathBuf> {
+        Ok(dirs::config_dir()
+            .context("Failed to get config directory")?
+            .join("Debeon"))
+    }
+
+    pub fn token_file(&self) -> Result<PathBuf> {
+        match &self.server.token_file {
+            Some(path) => Ok(path.clone()),
+            None => Ok(Self::config_dir()?.join("session.token")),
+        }
+    }
+
+    pub fn listen_address(&self) -> Result<SocketAddr> {
+        format!("{}:{}", self.server.bind_address, self.server.port)
+            .parse()
+            .with_context(|| {
+                format!(
+                    "Invalid listen address: {}:{}",
+                    self.server.bind_address, self.server.port
+                )
+            })
+    }
+}
+
+#[derive(Debug, Default)]
+struct CliOverrides {
+    settings_file: Option<PathBuf>,
+    overrides: Vec<(String, String)>,
+}
+
+impl CliOverrides {
+    /// Accepts `--settings <file>`, `--bind <addr>`, `--port <port>` and
+    /// `--set <section.key>=<value>`.
+    fn parse(args: &[String]) -> Result<Self> {
+        let mut cli = Self::default();
+        let mut args = args.iter();
+
+        while let Some(arg) = args.next() {
+            let mut value = || {
+                args.next()
+                    .cloned()
+                    .with_context(|| format!("Missing value for {}", arg))
+            };
+
+            match arg.as_str() {
+                "--settings" => cli.settings_file = Some(PathBuf::from(value()?)),
+                "--bind" => cli.overrides.push(("server.bind_address".to_string(), value()?)),
+                "--port" => cli.overrides.push(("server.port".to_string(), value()?)),
+                "--set" => {
+                    let assignment = value()?;
+                    let (path, value) = assignment
+                        .split_once('=')
+                        .with_context(|| format!("Expected key=value, got: {}", assignment))?;
+                    cli.overrides.push((path.trim().to_string(), value.trim().to_string()));
+                }
+                other => anyhow::bail!("Unknown argument: {}", other),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+/// Sets a dotted `section.key` path, parsing the value as TOML when possible
+/// so numbers and arrays keep their type.
+fn set_path(table: &mut toml::Table, path: &str, raw: &str) -> Result<()> {
+    let value = toml::from_str::<toml::Table>(&format!("value = {}", raw))
+        .ok()
+        .and_then(|mut t| t.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()));
+
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let key = segments.pop().context("Empty settings key")?;
+
+    let mut current = table;
+    for segment in segments {
+        current = current
+            .entry(segment.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()))
+            .as_table_mut()
+            .with_context(|| format!("Settings key is not a section: {}", segment))?;
+    }
+
+    current.insert(key.to_string(), value);
+    Ok(())
+}
@@ -1,17 +1,15 @@
-mod api;
-mod config;
-mod roblox;
 mod server;
-mod models;
 
 use anyhow::Result;
+use debeon_core::settings::DebeonSettings;
 use server::Server;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     println!("Debeon Backend Server Starting...");
 
-    let server = Server::new()?;
+    let settings = DebeonSettings::load()?;
+    let server = Server::new(settings)?;
     server.run().await?;
 
     Ok(())
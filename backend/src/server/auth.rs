@@ -0,0 +1,93 @@
+use anyhow::Result;
+use rand::RngCore;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
+use warp::Filter;
+
+#[derive(Debug)]
+pub struct Unauthorized;
+
+impl warp::reject::Reject for Unauthorized {}
+
+/// Random bearer token generated on every start and shared with the frontend
+/// through a file only the current user can read.
+#[derive(Clone)]
+pub struct SessionToken {
+    token: Arc<String>,
+}
+
+impl SessionToken {
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut bytes);
+
+        Self {
+            token: Arc::new(hex::encode(bytes)),
+        }
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Replace rather than truncate: an existing file may be readable by
+        // others, and `mode` only applies to a newly created file. The token
+        // is written only once the file exists with owner-only permissions.
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+            _ => {}
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+
+        // On Windows the file inherits the per-user ACL of %APPDATA%.
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        let mut file = options.open(path)?;
+        file.write_all(self.token.as_bytes())?;
+
+        Ok(())
+    }
+
+    fn matches(&self, header: &str) -> bool {
+        let candidate = match header.strip_prefix("Bearer ") {
+            Some(candidate) => candidate.trim().as_bytes(),
+            None => return false,
+        };
+        let expected = self.token.as_bytes();
+
+        if candidate.len() != expected.len() {
+            return false;
+        }
+
+        candidate
+            .iter()
+            .zip(expected)
+            .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+pub fn with_auth(
+    token: SessionToken,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("authorization")
+        .and_then(move |header: Option<String>| {
+            let token = token.clone();
+            async move {
+                match header {
+                    Some(header) if token.matches(&header) => Ok(()),
+                    _ => Err(warp::reject::custom(Unauthorized)),
+                }
+            }
+        })
+        .untuple_one()
+}
@@ -1,60 +1,142 @@
+mod auth;
+mod openapi;
+
 use anyhow::Result;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use utoipa::{IntoParams, OpenApi};
 use warp::{Filter, Reply};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use crate::roblox::RobloxManager;
-use crate::config::ConfigManager;
-use crate::api::{RobloxApiClient, AssetLoader};
-use crate::models::{RobloxConfig, ApiResponse};
+use debeon_core::roblox::{
+    FlagCatalog, FlagImporter, FlagSelector, InstallTarget, RobloxManager, UpstreamFlags,
+};
+use debeon_core::config::{ConfigManager, PresetLibrary};
+use debeon_core::api::{http_client, RobloxApiClient, AssetLoader};
+use debeon_core::audit::{self, AuditFilter};
+use debeon_core::error::DebeonError;
+use debeon_core::events::{DebeonEvent, EventBus, SettingsWatcher};
+use debeon_core::settings::DebeonSettings;
+use auth::{SessionToken, Unauthorized};
+use debeon_core::models::{
+    RobloxConfig, ApiResponse, AuditSource, CombineRequest, FlagRemoval, PresetRequest,
+    ProfileNameRequest, ReapplyStatus, OwnedRemoval, ApplyResult, SetFlagsResult,
+};
 
 pub struct Server {
     roblox_manager: Arc<RwLock<RobloxManager>>,
     config_manager: Arc<RwLock<ConfigManager>>,
+    presets: Arc<PresetLibrary>,
     api_client: Arc<RobloxApiClient>,
     asset_loader: Arc<AssetLoader>,
+    events: EventBus,
+    settings: DebeonSettings,
 }
 
 impl Server {
-    pub fn new() -> Result<Self> {
+    pub fn new(settings: DebeonSettings) -> Result<Self> {
+        let client = http_client()?;
+
         Ok(Self {
-            roblox_manager: Arc::new(RwLock::new(RobloxManager::new()?)),
+            roblox_manager: Arc::new(RwLock::new(RobloxManager::new(&settings, client.clone())?)),
             config_manager: Arc::new(RwLock::new(ConfigManager::new()?)),
-            api_client: Arc::new(RobloxApiClient::new()?),
-            asset_loader: Arc::new(AssetLoader::new()?),
+            presets: Arc::new(PresetLibrary::new()?),
+            api_client: Arc::new(RobloxApiClient::new(client.clone(), &settings.api)?),
+            asset_loader: Arc::new(AssetLoader::new(client, &settings.api, &settings.cache)?),
+            events: EventBus::new(),
+            settings,
         })
     }
 
     pub async fn run(self) -> Result<()> {
         let roblox_manager = self.roblox_manager.clone();
         let config_manager = self.config_manager.clone();
+        let presets = self.presets.clone();
         let api_client = self.api_client.clone();
         let asset_loader = self.asset_loader.clone();
+        let events = self.events.clone();
+
+        let reapply_enabled = self.settings.watcher.reapply;
+        SettingsWatcher::new(roblox_manager.clone(), events.clone(), reapply_enabled).spawn();
+
+        let token = SessionToken::generate();
+        let token_file = self.settings.token_file()?;
+        token.write_to(&token_file)?;
+        println!("Session token written to {}", token_file.display());
 
         let cors = warp::cors()
-            .allow_any_origin()
+            .allow_origins(self.settings.server.allowed_origins.iter().map(String::as_str))
             .allow_methods(vec!["GET", "POST", "PUT", "DELETE"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         let get_installations = warp::path!("api" / "installations")
             .and(warp::get())
             .and(with_roblox_manager(roblox_manager.clone()))
             .and_then(handle_get_installations);
 
+        let get_user_settings = warp::path!("api" / "user-settings")
+            .and(warp::get())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_get_user_settings);
+
         let get_config = warp::path!("api" / "config" / String)
+            .map(decode_path_segment)
             .and(warp::get())
             .and(with_config_manager(config_manager.clone()))
             .and_then(handle_get_config);
 
         let save_config = warp::path!("api" / "config" / String)
+            .map(decode_path_segment)
             .and(warp::post())
             .and(warp::body::json())
             .and(with_config_manager(config_manager.clone()))
             .and_then(handle_save_config);
 
+        let delete_config = warp::path!("api" / "config" / String)
+            .map(decode_path_segment)
+            .and(warp::delete())
+            .and(with_config_manager(config_manager.clone()))
+            .and_then(handle_delete_config);
+
+        let rename_config = warp::path!("api" / "config" / String / "rename")
+            .map(decode_path_segment)
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_config_manager(config_manager.clone()))
+            .and_then(handle_rename_config);
+
+        let duplicate_config = warp::path!("api" / "config" / String / "duplicate")
+            .map(decode_path_segment)
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_config_manager(config_manager.clone()))
+            .and_then(handle_duplicate_config);
+
+        let import_config = warp::path!("api" / "config" / String / "import")
+            .map(decode_path_segment)
+            .and(warp::post())
+            .and(warp::query::<ImportQuery>())
+            .and(warp::body::content_length_limit(1024 * 1024))
+            .and(warp::body::bytes())
+            .and(with_config_manager(config_manager.clone()))
+            .and_then(handle_import_config);
+
+        let export_config = warp::path!("api" / "config" / String / "export")
+            .map(decode_path_segment)
+            .and(warp::get())
+            .and(with_config_manager(config_manager.clone()))
+            .and_then(handle_export_config);
+
         let apply_config = warp::path!("api" / "apply")
             .and(warp::post())
+            .and(warp::query::<ApplyQuery>())
             .and(warp::body::json())
             .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
             .and_then(handle_apply_config);
 
         let list_profiles = warp::path!("api" / "profiles")
@@ -62,17 +144,99 @@ impl Server {
             .and(with_config_manager(config_manager.clone()))
             .and_then(handle_list_profiles);
 
+        let list_presets = warp::path!("api" / "presets")
+            .and(warp::get())
+            .and(with_presets(presets.clone()))
+            .and_then(handle_list_presets);
+
+        let get_preset = warp::path!("api" / "presets" / String)
+            .map(decode_path_segment)
+            .and(warp::get())
+            .and(with_presets(presets.clone()))
+            .and_then(handle_get_preset);
+
+        let save_preset = warp::path!("api" / "presets" / String)
+            .map(decode_path_segment)
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_presets(presets.clone()))
+            .and_then(handle_save_preset);
+
+        let delete_preset = warp::path!("api" / "presets" / String)
+            .map(decode_path_segment)
+            .and(warp::delete())
+            .and(with_presets(presets.clone()))
+            .and_then(handle_delete_preset);
+
         let get_flags = warp::path!("api" / "flags")
             .and(warp::get())
+            .and(warp::query::<InstallationQuery>())
             .and(with_roblox_manager(roblox_manager.clone()))
             .and_then(handle_get_flags);
 
         let set_flags = warp::path!("api" / "flags")
             .and(warp::post())
+            .and(warp::query::<SetFlagsQuery>())
             .and(warp::body::json())
             .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
             .and_then(handle_set_flags);
 
+        let flag_catalog = warp::path!("api" / "flags" / "catalog")
+            .and(warp::get())
+            .and(warp::query::<CatalogQuery>())
+            .and_then(handle_flag_catalog);
+
+        let import_flags = warp::path!("api" / "flags" / "import")
+            .and(warp::post())
+            .and(warp::query::<ImportFlagsQuery>())
+            .and(warp::body::content_length_limit(1024 * 1024))
+            .and(warp::body::bytes())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_config_manager(config_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_import_flags);
+
+        let combine_flags = warp::path!("api" / "flags" / "combine")
+            .and(warp::post())
+            .and(warp::body::json())
+            .and(with_presets(presets.clone()))
+            .and_then(handle_combine_flags);
+
+        let remove_flags = warp::path!("api" / "flags")
+            .and(warp::delete())
+            .and(warp::query::<RemoveFlagsQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_remove_flags);
+
+        let remove_flag = warp::path!("api" / "flags" / String)
+            .and(warp::delete())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_remove_flag);
+
+        let flag_ownership = warp::path!("api" / "flags" / "ownership")
+            .and(warp::get())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_flag_ownership);
+
+        let uninstall_flags = warp::path!("api" / "flags" / "uninstall")
+            .and(warp::post())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_uninstall_flags);
+
+        let remote_flags = warp::path!("api" / "flags" / "remote")
+            .and(warp::get())
+            .and(warp::query::<RemoteFlagsQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_api_client(api_client.clone()))
+            .and_then(handle_remote_flags);
+
         let get_user = warp::path!("api" / "user" / u64)
             .and(warp::get())
             .and(with_api_client(api_client.clone()))
@@ -86,27 +250,272 @@ impl Server {
         let download_asset = warp::path!("api" / "download" / "asset" / u64)
             .and(warp::get())
             .and(with_asset_loader(asset_loader.clone()))
+            .and(with_events(events.clone()))
             .and_then(handle_download_asset);
 
-        let routes = get_installations
-            .or(get_config)
+        let list_backups = warp::path!("api" / "backups")
+            .and(warp::get())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_list_backups);
+
+        let create_backup = warp::path!("api" / "backups")
+            .and(warp::post())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_create_backup);
+
+        let restore_backup = warp::path!("api" / "backups" / String / "restore")
+            .and(warp::post())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and(with_events(events.clone()))
+            .and_then(handle_restore_backup);
+
+        let diff_backup = warp::path!("api" / "backups" / String / "diff")
+            .and(warp::get())
+            .and(warp::query::<InstallationQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_diff_backup);
+
+        let get_audit = warp::path!("api" / "audit")
+            .and(warp::get())
+            .and(warp::query::<AuditQuery>())
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_get_audit);
+
+        let reapply_status = warp::path!("api" / "reapply")
+            .and(warp::get())
+            .and(warp::any().map(move || reapply_enabled))
+            .and(with_roblox_manager(roblox_manager.clone()))
+            .and_then(handle_reapply_status);
+
+        let event_stream = warp::path!("api" / "events")
+            .and(warp::get())
+            .and(with_events(events.clone()))
+            .map(handle_event_stream);
+
+        let openapi_json = warp::path!("api" / "openapi.json")
+            .and(warp::get())
+            .map(|| warp::reply::json(&openapi::ApiDoc::openapi()));
+
+        // Each area is boxed so the combined filter type stays shallow enough
+        // for the compiler as routes are added.
+        let config_routes = get_config
             .or(save_config)
-            .or(apply_config)
+            .or(delete_config)
+            .or(rename_config)
+            .or(duplicate_config)
+            .or(import_config)
+            .or(export_config)
             .or(list_profiles)
+            .boxed();
+
+        let flag_routes = apply_config
             .or(get_flags)
             .or(set_flags)
-            .or(get_user)
+            .or(flag_catalog)
+            .or(import_flags)
+            .or(combine_flags)
+            .or(remove_flags)
+            .or(remove_flag)
+            .or(flag_ownership)
+            .or(uninstall_flags)
+            .or(remote_flags)
+            .boxed();
+
+        let preset_routes = list_presets
+            .or(get_preset)
+            .or(save_preset)
+            .or(delete_preset)
+            .boxed();
+
+        let asset_routes = get_user
             .or(get_asset)
             .or(download_asset)
+            .boxed();
+
+        let backup_routes = list_backups
+            .or(create_backup)
+            .or(restore_backup)
+            .or(diff_backup)
+            .or(get_audit)
+            .or(reapply_status)
+            .boxed();
+
+        let api = get_installations
+            .or(get_user_settings)
+            .or(config_routes)
+            .or(flag_routes)
+            .or(preset_routes)
+            .or(asset_routes)
+            .or(backup_routes)
+            .or(event_stream)
+            .or(openapi_json);
+
+        let routes = auth::with_auth(token)
+            .and(api)
+            .recover(handle_rejection)
             .with(cors);
 
-        println!("Server running on http://127.0.0.1:8080");
-        warp::serve(routes).run(([127, 0, 0, 1], 8080)).await;
+        let address = self.settings.listen_address()?;
+        println!("Server running on http://{}", address);
+        warp::serve(routes).run(address).await;
 
         Ok(())
     }
 }
 
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct InstallationQuery {
+    /// Installation ID or "all"; defaults to the primary installation
+    installation: Option<String>,
+}
+
+impl InstallationQuery {
+    fn target(&self) -> InstallTarget {
+        InstallTarget::from_param(self.installation.as_deref())
+    }
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ImportQuery {
+    #[serde(default)]
+    overwrite: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ApplyQuery {
+    /// Return the diff that would be written instead of writing it
+    #[serde(default)]
+    dry_run: bool,
+    /// Profile the config was loaded from, recorded as the audit source
+    profile: Option<String>,
+    /// Installation ID or "all"; defaults to the primary installation
+    installation: Option<String>,
+    /// Replace the previous profile: first remove keys Debeon wrote that the
+    /// new config does not set, keeping hand-edited ones
+    #[serde(default)]
+    switch: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct SetFlagsQuery {
+    /// Return the diff that would be written instead of writing it
+    #[serde(default)]
+    dry_run: bool,
+    /// Preset the flags came from, recorded as the audit source
+    preset: Option<String>,
+    /// Installation ID or "all"; defaults to the primary installation
+    installation: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct CatalogQuery {
+    /// Case-insensitive text matched against name, category and description
+    #[serde(default)]
+    search: String,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct ImportFlagsQuery {
+    /// Merge into this profile's custom flags instead of the live ClientAppSettings.json
+    profile: Option<String>,
+    /// Only parse and report, without merging anywhere
+    #[serde(default)]
+    dry_run: bool,
+    /// Installation ID or "all" when merging into the live file; defaults to the primary installation
+    installation: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RemoveFlagsQuery {
+    /// Regular expression matched against flag names; omit to clear every flag
+    pattern: Option<String>,
+    /// Installation ID or "all"; defaults to the primary installation
+    installation: Option<String>,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RemoteFlagsQuery {
+    /// Installation ID; defaults to the primary installation
+    installation: Option<String>,
+    /// Client settings application; defaults to PCStudioApp for Studio and
+    /// PCDesktopClient otherwise
+    application: Option<String>,
+    /// Deployment channel; defaults to live
+    channel: Option<String>,
+    /// Download the published settings even if the cached copy is fresh
+    #[serde(default)]
+    refresh: bool,
+}
+
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct AuditQuery {
+    /// Earliest entry to return, as RFC 3339 or YYYY-MM-DD
+    since: Option<String>,
+    /// Latest entry to return, as RFC 3339 or YYYY-MM-DD (the whole day)
+    until: Option<String>,
+    /// Only entries that changed this flag
+    key: Option<String>,
+    /// Maximum number of entries, newest first
+    limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn filter(self) -> Result<AuditFilter> {
+        Ok(AuditFilter {
+            since: self.since.as_deref().map(audit::parse_time).transpose()?,
+            until: self.until.as_deref().map(audit::parse_until).transpose()?,
+            key: self.key,
+            limit: self.limit,
+        })
+    }
+}
+
+fn success_reply<T: Serialize>(data: T) -> warp::reply::WithStatus<warp::reply::Json> {
+    warp::reply::with_status(
+        warp::reply::json(&ApiResponse::success(data)),
+        warp::http::StatusCode::OK,
+    )
+}
+
+fn error_reply(error: impl Into<DebeonError>) -> warp::reply::WithStatus<warp::reply::Json> {
+    let error = error.into();
+    warp::reply::with_status(
+        warp::reply::json(&ApiResponse::<()>::error(&error)),
+        error.status_code(),
+    )
+}
+
+async fn handle_rejection(rejection: warp::Rejection) -> Result<impl Reply, warp::Rejection> {
+    let error = if rejection.find::<Unauthorized>().is_some() {
+        DebeonError::Unauthorized("Missing or invalid session token".to_string())
+    } else if let Some(e) = rejection.find::<warp::filters::body::BodyDeserializeError>() {
+        DebeonError::Validation(e.to_string())
+    } else if let Some(e) = rejection.find::<warp::reject::InvalidQuery>() {
+        DebeonError::Validation(e.to_string())
+    } else if let Some(e) = rejection.find::<warp::reject::PayloadTooLarge>() {
+        DebeonError::Validation(e.to_string())
+    } else if rejection.is_not_found() {
+        DebeonError::NotFound("Route not found".to_string())
+    } else {
+        return Err(rejection);
+    };
+
+    Ok(error_reply(error))
+}
+
 fn with_roblox_manager(
     manager: Arc<RwLock<RobloxManager>>,
 ) -> impl Filter<Extract = (Arc<RwLock<RobloxManager>>,), Error = std::convert::Infallible> + Clone {
@@ -119,6 +528,51 @@ fn with_config_manager(
     warp::any().map(move || manager.clone())
 }
 
+/// Preset names are free text, so undo the percent-encoding warp leaves on
+/// path segments.
+fn decode_path_segment(segment: String) -> String {
+    percent_decode_str(&segment).decode_utf8_lossy().into_owned()
+}
+
+/// Characters RFC 5987 allows unencoded in an extended header parameter.
+const ATTR_CHAR: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'!')
+    .remove(b'#')
+    .remove(b'$')
+    .remove(b'&')
+    .remove(b'+')
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'^')
+    .remove(b'_')
+    .remove(b'`')
+    .remove(b'|')
+    .remove(b'~');
+
+/// `Content-Disposition` for a download named `filename`: a quoted ASCII
+/// fallback for old clients plus the exact UTF-8 name as `filename*`.
+fn attachment_disposition(filename: &str) -> String {
+    let fallback: String = filename
+        .chars()
+        .map(|c| {
+            let plain = (c.is_ascii_graphic() || c == ' ') && c != '"' && c != '\\';
+            if plain { c } else { '_' }
+        })
+        .collect();
+
+    format!(
+        "attachment; filename=\"{}\"; filename*=UTF-8''{}",
+        fallback,
+        utf8_percent_encode(filename, ATTR_CHAR)
+    )
+}
+
+fn with_presets(
+    presets: Arc<PresetLibrary>,
+) -> impl Filter<Extract = (Arc<PresetLibrary>,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || presets.clone())
+}
+
 fn with_api_client(
     client: Arc<RobloxApiClient>,
 ) -> impl Filter<Extract = (Arc<RobloxApiClient>,), Error = std::convert::Infallible> + Clone {
@@ -131,107 +585,994 @@ fn with_asset_loader(
     warp::any().map(move || loader.clone())
 }
 
+fn with_events(
+    events: EventBus,
+) -> impl Filter<Extract = (EventBus,), Error = std::convert::Infallible> + Clone {
+    warp::any().map(move || events.clone())
+}
+
+#[utoipa::path(
+    get,
+    tag = "roblox",
+    path = "/api/installations",
+    responses(
+        (status = 200, description = "Detected installations", body = InstallationsResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_get_installations(
     manager: Arc<RwLock<RobloxManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.find_installations() {
-        Ok(installations) => Ok(warp::reply::json(&ApiResponse::success(installations))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match RobloxManager::run_blocking(&manager, |m| m.find_installations()).await {
+        Ok(installations) => Ok(success_reply(installations)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "roblox",
+    path = "/api/user-settings",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Properties of Roblox's user settings XML for the installation", body = UserSettingsResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_get_user_settings(
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.user_settings(&target)).await {
+        Ok(settings) => Ok(success_reply(settings)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    get,
+    tag = "profiles",
+    path = "/api/config/{name}",
+    params(("name" = String, Path, description = "Profile name")),
+    responses(
+        (status = 200, description = "Profile", body = ConfigResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_get_config(
     name: String,
     manager: Arc<RwLock<ConfigManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.load_config(&name) {
-        Ok(config) => Ok(warp::reply::json(&ApiResponse::success(config))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match ConfigManager::run_blocking(&manager, move |m| m.load_config(&name)).await {
+        Ok(config) => Ok(success_reply(config)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    post,
+    tag = "profiles",
+    path = "/api/config/{name}",
+    params(("name" = String, Path, description = "Profile name")),
+    request_body = RobloxConfig,
+    responses(
+        (status = 200, description = "Profile saved", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_save_config(
     name: String,
     config: RobloxConfig,
     manager: Arc<RwLock<ConfigManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.save_config(&name, &config) {
-        Ok(_) => Ok(warp::reply::json(&ApiResponse::success("Config saved"))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match ConfigManager::run_blocking(&manager, move |m| m.save_config(&name, &config)).await {
+        Ok(_) => Ok(success_reply("Config saved")),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    tag = "profiles",
+    path = "/api/config/{name}",
+    params(("name" = String, Path, description = "Profile name")),
+    responses(
+        (status = 200, description = "Profile deleted", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_delete_config(
+    name: String,
+    manager: Arc<RwLock<ConfigManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    match ConfigManager::run_blocking(&manager, move |m| m.delete_profile(&name)).await {
+        Ok(_) => Ok(success_reply("Config deleted")),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "profiles",
+    path = "/api/config/{name}/rename",
+    params(("name" = String, Path, description = "Profile name")),
+    request_body = ProfileNameRequest,
+    responses(
+        (status = 200, description = "Profile renamed", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_rename_config(
+    name: String,
+    request: ProfileNameRequest,
+    manager: Arc<RwLock<ConfigManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let renamed = ConfigManager::run_blocking(&manager, move |m| {
+        m.rename_profile(&name, &request.new_name)
+    });
+    match renamed.await {
+        Ok(_) => Ok(success_reply("Config renamed")),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "profiles",
+    path = "/api/config/{name}/duplicate",
+    params(("name" = String, Path, description = "Profile name")),
+    request_body = ProfileNameRequest,
+    responses(
+        (status = 200, description = "Profile duplicated", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_duplicate_config(
+    name: String,
+    request: ProfileNameRequest,
+    manager: Arc<RwLock<ConfigManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let duplicated = ConfigManager::run_blocking(&manager, move |m| {
+        m.duplicate_profile(&name, &request.new_name)
+    });
+    match duplicated.await {
+        Ok(_) => Ok(success_reply("Config duplicated")),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    post,
+    tag = "profiles",
+    path = "/api/config/{name}/import",
+    params(("name" = String, Path, description = "Profile name"), ImportQuery),
+    request_body = RobloxConfig,
+    responses(
+        (status = 200, description = "Profile imported", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_import_config(
+    name: String,
+    query: ImportQuery,
+    body: bytes::Bytes,
+    manager: Arc<RwLock<ConfigManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let content = match std::str::from_utf8(&body) {
+        Ok(content) => content.to_string(),
+        Err(_) => {
+            return Ok(error_reply(DebeonError::Validation(
+                "Uploaded profile is not valid UTF-8".to_string(),
+            )))
+        }
+    };
+
+    let imported = ConfigManager::run_blocking(&manager, move |m| {
+        m.import_config_str(&content, &name, query.overwrite)
+    });
+    match imported.await {
+        Ok(_) => Ok(success_reply("Config imported")),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "profiles",
+    path = "/api/config/{name}/export",
+    params(("name" = String, Path, description = "Profile name")),
+    responses(
+        (status = 200, description = "Profile file download", body = RobloxConfig),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_export_config(
+    name: String,
+    manager: Arc<RwLock<ConfigManager>>,
+) -> Result<warp::reply::Response, warp::Rejection> {
+    let profile = name.clone();
+    match ConfigManager::run_blocking(&manager, move |m| m.export_config_string(&profile)).await {
+        Ok(content) => Ok(warp::reply::with_header(
+            warp::reply::with_header(content, "Content-Type", "application/json"),
+            "Content-Disposition",
+            attachment_disposition(&format!("{}.json", name)),
+        )
+        .into_response()),
+        Err(e) => Ok(error_reply(e).into_response()),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "roblox",
+    path = "/api/apply",
+    params(ApplyQuery),
+    request_body = RobloxConfig,
+    responses(
+        (status = 200, description = "Installations written and which config fields were honored (ConfigApplied); with dry_run a FlagDiff of the pending changes; with switch an OwnedRemoval", body = ApplyResultResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_apply_config(
+    query: ApplyQuery,
     config: RobloxConfig,
     manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.apply_config(&config) {
-        Ok(_) => Ok(warp::reply::json(&ApiResponse::success("Config applied"))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    let target = InstallTarget::from_param(query.installation.as_deref());
+
+    if query.dry_run {
+        let switch = query.switch;
+        let preview = RobloxManager::run_blocking(&manager, move |m| {
+            if switch {
+                m.preview_switch(&target, &config)
+            } else {
+                m.preview_config(&target, &config)
+            }
+        });
+        return match preview.await {
+            Ok(diff) => Ok(success_reply(ApplyResult::Preview(diff))),
+            Err(e) => Ok(error_reply(e)),
+        };
+    }
+
+    let source = match query.profile {
+        Some(name) => AuditSource::Profile { name },
+        None => AuditSource::Manual,
+    };
+
+    if query.switch {
+        let switched = RobloxManager::run_blocking(&manager, move |m| {
+            m.switch_config(&target, &config, source)
+        });
+        return match switched.await {
+            Ok(removal) => {
+                publish_owned_removal(&events, &removal);
+                events.publish(DebeonEvent::ConfigApplied);
+                Ok(success_reply(ApplyResult::Switched(removal)))
+            }
+            Err(e) => Ok(error_reply(e)),
+        };
+    }
+
+    match RobloxManager::run_blocking(&manager, move |m| m.apply_config(&target, &config, source)).await {
+        Ok(applied) => {
+            events.publish(DebeonEvent::ConfigApplied);
+            Ok(success_reply(ApplyResult::Applied(applied)))
+        }
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    get,
+    tag = "profiles",
+    path = "/api/profiles",
+    responses(
+        (status = 200, description = "Profile names", body = ProfilesResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_list_profiles(
     manager: Arc<RwLock<ConfigManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.list_profiles() {
-        Ok(profiles) => Ok(warp::reply::json(&ApiResponse::success(profiles))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match ConfigManager::run_blocking(&manager, |m| m.list_profiles()).await {
+        Ok(profiles) => Ok(success_reply(profiles)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "presets",
+    path = "/api/presets",
+    responses(
+        (status = 200, description = "Built-in presets followed by user presets", body = PresetsResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_list_presets(
+    presets: Arc<PresetLibrary>,
+) -> Result<impl Reply, warp::Rejection> {
+    match PresetLibrary::run_blocking(&presets, |p| p.list_presets()).await {
+        Ok(presets) => Ok(success_reply(presets)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "presets",
+    path = "/api/presets/{name}",
+    params(("name" = String, Path, description = "Preset name")),
+    responses(
+        (status = 200, description = "Preset", body = PresetResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_get_preset(
+    name: String,
+    presets: Arc<PresetLibrary>,
+) -> Result<impl Reply, warp::Rejection> {
+    match PresetLibrary::run_blocking(&presets, move |p| p.get_preset(&name)).await {
+        Ok(preset) => Ok(success_reply(preset)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    post,
+    tag = "presets",
+    path = "/api/presets/{name}",
+    params(("name" = String, Path, description = "Preset name")),
+    request_body = PresetRequest,
+    responses(
+        (status = 200, description = "User preset created or updated", body = PresetResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_save_preset(
+    name: String,
+    request: PresetRequest,
+    presets: Arc<PresetLibrary>,
+) -> Result<impl Reply, warp::Rejection> {
+    match PresetLibrary::run_blocking(&presets, move |p| p.save_preset(&name, &request)).await {
+        Ok(preset) => Ok(success_reply(preset)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    tag = "presets",
+    path = "/api/presets/{name}",
+    params(("name" = String, Path, description = "Preset name")),
+    responses(
+        (status = 200, description = "User preset deleted", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_delete_preset(
+    name: String,
+    presets: Arc<PresetLibrary>,
+) -> Result<impl Reply, warp::Rejection> {
+    match PresetLibrary::run_blocking(&presets, move |p| p.delete_preset(&name)).await {
+        Ok(_) => Ok(success_reply("Preset deleted")),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "flags",
+    path = "/api/flags",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Current fast flags", body = FlagsResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_get_flags(
+    query: InstallationQuery,
     manager: Arc<RwLock<RobloxManager>>,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.get_fast_flags() {
-        Ok(flags) => Ok(warp::reply::json(&ApiResponse::success(flags))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.get_fast_flags(&target)).await {
+        Ok(flags) => Ok(success_reply(flags)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    post,
+    tag = "flags",
+    path = "/api/flags",
+    params(SetFlagsQuery),
+    request_body = HashMap<String, String>,
+    responses(
+        (status = 200, description = "Flags applied, with catalog warnings (FlagsApplied); with dry_run a FlagDiff of the pending changes", body = SetFlagsResultResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_set_flags(
-    flags: std::collections::HashMap<String, String>,
+    query: SetFlagsQuery,
+    flags: HashMap<String, String>,
     manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
 ) -> Result<impl Reply, warp::Rejection> {
-    let manager = manager.read().await;
-    match manager.set_fast_flags(&flags) {
-        Ok(_) => Ok(warp::reply::json(&ApiResponse::success("Flags applied"))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    let target = InstallTarget::from_param(query.installation.as_deref());
+
+    if query.dry_run {
+        return match RobloxManager::run_blocking(&manager, move |m| m.preview_fast_flags(&target, &flags)).await {
+            Ok(diff) => Ok(success_reply(SetFlagsResult::Preview(diff))),
+            Err(e) => Ok(error_reply(e)),
+        };
+    }
+
+    let source = match query.preset {
+        Some(name) => AuditSource::Preset { name },
+        None => AuditSource::Manual,
+    };
+
+    match RobloxManager::run_blocking(&manager, move |m| m.set_fast_flags(&target, &flags, source)).await {
+        Ok(applied) => {
+            events.publish(DebeonEvent::FlagsChanged { keys: applied.applied.clone() });
+            Ok(success_reply(SetFlagsResult::Applied(applied)))
+        }
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "flags",
+    path = "/api/flags/catalog",
+    params(CatalogQuery),
+    responses(
+        (status = 200, description = "Known flags with type, category, description and range", body = FlagCatalogResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_flag_catalog(query: CatalogQuery) -> Result<impl Reply, warp::Rejection> {
+    Ok(success_reply(FlagCatalog::search(&query.search)))
+}
+
+#[utoipa::path(
+    post,
+    tag = "flags",
+    path = "/api/flags/import",
+    params(ImportFlagsQuery),
+    request_body(content = String, description = "Bloxstrap/Fishstrap ClientAppSettings.json, Sober config.json, or KEY=VALUE lines", content_type = "text/plain"),
+    responses(
+        (status = 200, description = "Detected format, imported flags, skipped entries and catalog warnings", body = FlagImportResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_import_flags(
+    query: ImportFlagsQuery,
+    body: bytes::Bytes,
+    roblox_manager: Arc<RwLock<RobloxManager>>,
+    config_manager: Arc<RwLock<ConfigManager>>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let content = match std::str::from_utf8(&body) {
+        Ok(content) => content,
+        Err(_) => {
+            return Ok(error_reply(DebeonError::Validation(
+                "Uploaded flags are not valid UTF-8".to_string(),
+            )))
+        }
+    };
+
+    let import = FlagImporter::parse(content);
+
+    if query.dry_run {
+        return Ok(success_reply(import));
+    }
+
+    if import.flags.is_empty() {
+        return Ok(error_reply(DebeonError::Validation(format!(
+            "No importable flags found ({} entries skipped)",
+            import.skipped.len()
+        ))));
+    }
+
+    let result = match query.profile {
+        Some(profile) => {
+            let flags = import.flags.clone();
+            ConfigManager::run_blocking(&config_manager, move |m| m.merge_custom_flags(&profile, &flags))
+                .await
+        }
+        None => {
+            let flags: HashMap<String, String> = import.flags.clone().into_iter().collect();
+            let keys = import.flags.keys().cloned().collect();
+            let source = AuditSource::Import { format: import.format };
+            let target = InstallTarget::from_param(query.installation.as_deref());
+            RobloxManager::run_blocking(&roblox_manager, move |m| m.set_fast_flags(&target, &flags, source))
+                .await
+                .map(|_| events.publish(DebeonEvent::FlagsChanged { keys }))
+        }
+    };
+
+    match result {
+        Ok(_) => Ok(success_reply(import)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "flags",
+    path = "/api/flags/combine",
+    request_body = CombineRequest,
+    responses(
+        (status = 200, description = "Merged flags and every conflict with how it was resolved", body = FlagCombinationResponse),
+        (status = "default", description = "Error with a machine-readable code; `conflict` when the policy is error and sources disagree", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_combine_flags(
+    request: CombineRequest,
+    presets: Arc<PresetLibrary>,
+) -> Result<impl Reply, warp::Rejection> {
+    match PresetLibrary::run_blocking(&presets, move |p| p.combine(&request)).await {
+        Ok(combination) => Ok(success_reply(combination)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    tag = "flags",
+    path = "/api/flags",
+    params(RemoveFlagsQuery),
+    responses(
+        (status = 200, description = "Removed flags and the backup taken beforehand", body = FlagRemovalResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_remove_flags(
+    query: RemoveFlagsQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let selector = match query.pattern.as_deref() {
+        Some(pattern) => match FlagSelector::pattern(pattern) {
+            Ok(selector) => selector,
+            Err(e) => return Ok(error_reply(e)),
+        },
+        None => FlagSelector::All,
+    };
+
+    let target = InstallTarget::from_param(query.installation.as_deref());
+
+    match remove_flags(&manager, &events, target, selector).await {
+        Ok(removal) => Ok(success_reply(removal)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    tag = "flags",
+    path = "/api/flags/{name}",
+    params(("name" = String, Path, description = "Flag name"), InstallationQuery),
+    responses(
+        (status = 200, description = "Removed flag and the backup taken beforehand", body = FlagRemovalResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_remove_flag(
+    name: String,
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let selector = FlagSelector::Keys(vec![name.clone()]);
+
+    match remove_flags(&manager, &events, query.target(), selector).await {
+        Ok(removal) if removal.removed.is_empty() => Ok(error_reply(DebeonError::NotFound(
+            format!("Flag not set: {}", name),
+        ))),
+        Ok(removal) => Ok(success_reply(removal)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    get,
+    tag = "flags",
+    path = "/api/flags/ownership",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Keys Debeon wrote, keys changed by hand since, and keys Debeon never wrote", body = FlagOwnershipResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_flag_ownership(
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.flag_ownership(&target)).await {
+        Ok(ownership) => Ok(success_reply(ownership)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "flags",
+    path = "/api/flags/uninstall",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Removed Debeon's keys; hand-edited and unowned keys are reported and kept", body = OwnedRemovalResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_uninstall_flags(
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.uninstall_changes(&target)).await {
+        Ok(removal) => {
+            publish_owned_removal(&events, &removal);
+            Ok(success_reply(removal))
+        }
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "flags",
+    path = "/api/flags/remote",
+    params(RemoteFlagsQuery),
+    responses(
+        (status = 200, description = "Each local flag with its published value, whether Roblox still ships it and whether the override changes anything", body = RemoteComparisonResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_remote_flags(
+    query: RemoteFlagsQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    client: Arc<RobloxApiClient>,
+) -> Result<impl Reply, warp::Rejection> {
+    let target = InstallTarget::from_param(query.installation.as_deref());
+
+    let local = RobloxManager::run_blocking(&manager, move |m| {
+        Ok((m.single_target(&target)?, m.get_fast_flags(&target)?))
+    })
+    .await;
+
+    let (installation, flags) = match local {
+        Ok(local) => local,
+        Err(e) => return Ok(error_reply(e)),
+    };
+
+    let application = query
+        .application
+        .unwrap_or_else(|| UpstreamFlags::default_application(&installation).to_string());
+    let channel = query.channel.unwrap_or_else(|| "live".to_string());
+
+    match client.get_client_settings(&application, &channel, query.refresh).await {
+        Ok(remote) => Ok(success_reply(UpstreamFlags::compare(&installation.id, &flags, &remote))),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+fn publish_owned_removal(events: &EventBus, removal: &OwnedRemoval) {
+    for backup in &removal.backups {
+        events.publish(DebeonEvent::BackupCreated { backup: backup.clone() });
+    }
+    if !removal.removed.is_empty() {
+        events.publish(DebeonEvent::FlagsChanged { keys: removal.removed.clone() });
+    }
+}
+
+async fn remove_flags(
+    manager: &Arc<RwLock<RobloxManager>>,
+    events: &EventBus,
+    target: InstallTarget,
+    selector: FlagSelector,
+) -> Result<FlagRemoval> {
+    let removal = RobloxManager::run_blocking(manager, move |m| {
+        m.remove_fast_flags(&target, &selector, AuditSource::Manual)
+    })
+    .await?;
+
+    for backup in &removal.backups {
+        events.publish(DebeonEvent::BackupCreated { backup: backup.clone() });
+    }
+    if !removal.removed.is_empty() {
+        events.publish(DebeonEvent::FlagsChanged { keys: removal.removed.clone() });
+    }
+
+    Ok(removal)
+}
+
+#[utoipa::path(
+    get,
+    tag = "assets",
+    path = "/api/user/{id}",
+    params(("id" = u64, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "Roblox user", body = UserResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_get_user(
     user_id: u64,
     client: Arc<RobloxApiClient>,
 ) -> Result<impl Reply, warp::Rejection> {
-    match client.get_user_info(user_id) {
-        Ok(user) => Ok(warp::reply::json(&ApiResponse::success(user))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match client.get_user_info(user_id).await {
+        Ok(user) => Ok(success_reply(user)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    get,
+    tag = "assets",
+    path = "/api/asset/{id}",
+    params(("id" = u64, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Asset details", body = AssetResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_get_asset(
     asset_id: u64,
     client: Arc<RobloxApiClient>,
 ) -> Result<impl Reply, warp::Rejection> {
-    match client.get_asset_details(asset_id) {
-        Ok(asset) => Ok(warp::reply::json(&ApiResponse::success(asset))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    match client.get_asset_details(asset_id).await {
+        Ok(asset) => Ok(success_reply(asset)),
+        Err(e) => Ok(error_reply(e)),
     }
 }
 
+#[utoipa::path(
+    get,
+    tag = "assets",
+    path = "/api/download/asset/{id}",
+    params(("id" = u64, Path, description = "Asset ID")),
+    responses(
+        (status = 200, description = "Base64-encoded asset data", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
 async fn handle_download_asset(
     asset_id: u64,
     loader: Arc<AssetLoader>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let on_progress = |downloaded, total| {
+        events.publish(DebeonEvent::DownloadProgress { asset_id, downloaded, total });
+    };
+
+    match loader.download_asset_with_progress(asset_id, on_progress).await {
+        Ok(bytes) => Ok(success_reply(BASE64.encode(bytes))),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "backups",
+    path = "/api/backups",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Backups, newest first; filtered to one installation when `installation` is an ID", body = BackupsResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_list_backups(
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    // Unlike other routes, listing defaults to every installation's backups.
+    let target = match query.installation {
+        Some(_) => query.target(),
+        None => InstallTarget::All,
+    };
+
+    match RobloxManager::run_blocking(&manager, move |m| m.list_backups(&target)).await {
+        Ok(backups) => Ok(success_reply(backups)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    post,
+    tag = "backups",
+    path = "/api/backups",
+    params(InstallationQuery),
+    responses(
+        (status = 200, description = "Backup created", body = BackupResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_create_backup(
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
 ) -> Result<impl Reply, warp::Rejection> {
-    match loader.download_asset(asset_id) {
-        Ok(bytes) => Ok(warp::reply::json(&ApiResponse::success(base64::encode(bytes)))),
-        Err(e) => Ok(warp::reply::json(&ApiResponse::<()>::error(e.to_string()))),
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.backup_config(&target)).await {
+        Ok(Some(backup)) => {
+            events.publish(DebeonEvent::BackupCreated { backup: backup.clone() });
+            Ok(success_reply(backup))
+        }
+        Ok(None) => Ok(error_reply(DebeonError::NotFound(
+            "No ClientAppSettings.json to back up".to_string(),
+        ))),
+        Err(e) => Ok(error_reply(e)),
     }
 }
+
+#[utoipa::path(
+    post,
+    tag = "backups",
+    path = "/api/backups/{name}/restore",
+    params(("name" = String, Path, description = "Backup file name"), InstallationQuery),
+    responses(
+        (status = 200, description = "Backup restored", body = MessageResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_restore_backup(
+    name: String,
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+    events: EventBus,
+) -> Result<impl Reply, warp::Rejection> {
+    let backup_name = name.clone();
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.restore_config(&target, &backup_name)).await {
+        Ok(installations) => {
+            events.publish(DebeonEvent::BackupRestored { name });
+            Ok(success_reply(format!("Backup restored to {}", installations.join(", "))))
+        }
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "backups",
+    path = "/api/backups/{name}/diff",
+    params(("name" = String, Path, description = "Backup file name"), InstallationQuery),
+    responses(
+        (status = 200, description = "Changes from the backup to the live file", body = FlagDiffResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_diff_backup(
+    name: String,
+    query: InstallationQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let target = query.target();
+
+    match RobloxManager::run_blocking(&manager, move |m| m.diff_backup(&target, &name)).await {
+        Ok(diff) => Ok(success_reply(diff)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "audit",
+    path = "/api/audit",
+    params(AuditQuery),
+    responses(
+        (status = 200, description = "Audit entries for ClientAppSettings.json writes, newest first", body = AuditEntriesResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_get_audit(
+    query: AuditQuery,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    let filter = match query.filter() {
+        Ok(filter) => filter,
+        Err(e) => return Ok(error_reply(e)),
+    };
+
+    match RobloxManager::run_blocking(&manager, move |m| m.audit_log().query(&filter)).await {
+        Ok(entries) => Ok(success_reply(entries)),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "audit",
+    path = "/api/reapply",
+    responses(
+        (status = 200, description = "Profile and flags remembered per channel, and what the watcher re-applied", body = ReapplyStatusResponse),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+async fn handle_reapply_status(
+    enabled: bool,
+    manager: Arc<RwLock<RobloxManager>>,
+) -> Result<impl Reply, warp::Rejection> {
+    match RobloxManager::run_blocking(&manager, |m| m.reapply_status()).await {
+        Ok(status) => Ok(success_reply(ReapplyStatus { enabled, ..status })),
+        Err(e) => Ok(error_reply(e)),
+    }
+}
+
+#[utoipa::path(
+    get,
+    tag = "events",
+    path = "/api/events",
+    responses(
+        (status = 200, description = "Server-sent events", content_type = "text/event-stream", body = DebeonEvent),
+        (status = "default", description = "Error with a machine-readable code", body = MessageResponse),
+    ),
+    security(("bearer" = [])),
+)]
+fn handle_event_stream(events: EventBus) -> impl Reply {
+    let receiver = events.subscribe();
+
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        // A client that falls behind is told how many events it missed
+        // instead of silently skipping them.
+        let event = match receiver.recv().await {
+            Ok(event) => event,
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(count)) => {
+                DebeonEvent::EventsDropped { count }
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => return None,
+        };
+        let sse_event = warp::sse::Event::default()
+            .event(event.kind())
+            .json_data(&event);
+        Some((sse_event, receiver))
+    });
+
+    warp::sse::reply(warp::sse::keep_alive().stream(stream))
+}
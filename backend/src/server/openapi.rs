@@ -0,0 +1,166 @@
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+use debeon_core::api::{AssetDetails, Creator, GameInfo, UserInfo};
+use debeon_core::events::DebeonEvent;
+use debeon_core::models::{
+    AppliedState, ApplyResult, AudioConfig, AuditChange, AuditEntry, AuditOperation,
+    AuditSource, BackupInfo, CombineRequest, ConfigApplied, ConfigCoverage, ConflictPolicy,
+    ControlsConfig, FlagCatalogEntry, FlagChange, FlagCombination, FlagConflict, FlagDiff,
+    FlagImport, FlagOwnership, FlagPreset, FlagRemoval, FlagSourceValue, FlagWarning,
+    FlagWarningKind, FlagsApplied, GraphicsConfig, ImportFormat, MappedField, NetworkConfig,
+    OwnedRemoval, PerformanceConfig, PresetRequest, ProfileNameRequest, ReapplyReason,
+    ReapplyReport, ReapplyStatus, RemoteComparison, RemoteFlagStatus, RenderingConfig,
+    RobloxConfig, RobloxInstallation, SetFlagsResult, SkippedEntry, UIConfig, UnbackedField,
+    UserSetting, UserSettingChange, UserSettings, UserSettingsPreview, UserSettingsWrite,
+    ApplyResultResponse, AssetResponse, AuditEntriesResponse, BackupResponse, BackupsResponse,
+    ConfigAppliedResponse, ConfigResponse, FlagCatalogResponse, FlagCombinationResponse,
+    FlagDiffResponse, FlagImportResponse, FlagOwnershipResponse, FlagRemovalResponse,
+    FlagsAppliedResponse, FlagsResponse, InstallationsResponse, MessageResponse,
+    OwnedRemovalResponse, PresetResponse, PresetsResponse, ProfilesResponse,
+    ReapplyStatusResponse, RemoteComparisonResponse, SetFlagsResultResponse, UserResponse,
+    UserSettingsResponse,
+};
+use debeon_core::roblox::FlagKind;
+
+/// OpenAPI 3 description of every route, served at `/api/openapi.json`.
+/// Add new handlers to `paths` and new models to `schemas` alongside the route.
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Debeon Backend API", description = "Local REST API used by the Debeon frontend."),
+    paths(
+        super::handle_get_installations,
+        super::handle_get_user_settings,
+        super::handle_get_config,
+        super::handle_save_config,
+        super::handle_delete_config,
+        super::handle_rename_config,
+        super::handle_duplicate_config,
+        super::handle_import_config,
+        super::handle_export_config,
+        super::handle_apply_config,
+        super::handle_list_profiles,
+        super::handle_list_presets,
+        super::handle_get_preset,
+        super::handle_save_preset,
+        super::handle_delete_preset,
+        super::handle_get_flags,
+        super::handle_set_flags,
+        super::handle_flag_catalog,
+        super::handle_import_flags,
+        super::handle_combine_flags,
+        super::handle_remove_flags,
+        super::handle_remove_flag,
+        super::handle_flag_ownership,
+        super::handle_uninstall_flags,
+        super::handle_remote_flags,
+        super::handle_get_user,
+        super::handle_get_asset,
+        super::handle_download_asset,
+        super::handle_list_backups,
+        super::handle_create_backup,
+        super::handle_restore_backup,
+        super::handle_diff_backup,
+        super::handle_get_audit,
+        super::handle_reapply_status,
+        super::handle_event_stream,
+    ),
+    components(schemas(
+        RobloxConfig,
+        GraphicsConfig,
+        AudioConfig,
+        ControlsConfig,
+        NetworkConfig,
+        RenderingConfig,
+        PerformanceConfig,
+        UIConfig,
+        RobloxInstallation,
+        BackupInfo,
+        FlagChange,
+        FlagDiff,
+        FlagRemoval,
+        FlagOwnership,
+        OwnedRemoval,
+        ConfigApplied,
+        ApplyResult,
+        SetFlagsResult,
+        ConfigCoverage,
+        MappedField,
+        UnbackedField,
+        UserSetting,
+        UserSettings,
+        UserSettingChange,
+        UserSettingsPreview,
+        UserSettingsWrite,
+        RemoteComparison,
+        RemoteFlagStatus,
+        ConflictPolicy,
+        FlagSourceValue,
+        FlagConflict,
+        FlagCombination,
+        CombineRequest,
+        FlagPreset,
+        PresetRequest,
+        FlagKind,
+        FlagCatalogEntry,
+        FlagWarningKind,
+        FlagWarning,
+        FlagsApplied,
+        ImportFormat,
+        SkippedEntry,
+        FlagImport,
+        AuditOperation,
+        AuditSource,
+        AuditChange,
+        AuditEntry,
+        ReapplyReason,
+        AppliedState,
+        ReapplyReport,
+        ReapplyStatus,
+        ProfileNameRequest,
+        UserInfo,
+        GameInfo,
+        Creator,
+        AssetDetails,
+        DebeonEvent,
+        MessageResponse,
+        ProfilesResponse,
+        ConfigResponse,
+        InstallationsResponse,
+        FlagsResponse,
+        BackupResponse,
+        BackupsResponse,
+        FlagDiffResponse,
+        FlagRemovalResponse,
+        FlagOwnershipResponse,
+        OwnedRemovalResponse,
+        ConfigAppliedResponse,
+        ApplyResultResponse,
+        SetFlagsResultResponse,
+        UserSettingsResponse,
+        RemoteComparisonResponse,
+        PresetResponse,
+        PresetsResponse,
+        FlagCombinationResponse,
+        FlagCatalogResponse,
+        FlagsAppliedResponse,
+        FlagImportResponse,
+        AuditEntriesResponse,
+        ReapplyStatusResponse,
+        UserResponse,
+        AssetResponse,
+    )),
+    modifiers(&BearerAuth),
+)]
+pub struct ApiDoc;
+
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
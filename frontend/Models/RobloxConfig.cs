@@ -1,4 +1,5 @@
 using System.Collections.Generic;
+using System.Text.Json.Serialization;
 
 namespace Debeon.Models
 {
@@ -105,13 +106,49 @@ namespace Debeon.Models
         public bool Success { get; set; }
         public T Data { get; set; }
         public string Error { get; set; }
+        public string Code { get; set; }
+    }
+
+    public class FlagPreset
+    {
+        public string Name { get; set; }
+        public string Category { get; set; }
+        public string Description { get; set; }
+        public Dictionary<string, string> Flags { get; set; } = new Dictionary<string, string>();
+        public bool Builtin { get; set; }
+    }
+
+    public class FlagWarning
+    {
+        public string Key { get; set; }
+        public string Kind { get; set; }
+        public string Message { get; set; }
+        public List<string> Suggestions { get; set; } = new List<string>();
+    }
+
+    public class FlagsApplied
+    {
+        public List<string> Applied { get; set; } = new List<string>();
+        public List<FlagWarning> Warnings { get; set; } = new List<FlagWarning>();
+        public List<string> Installations { get; set; } = new List<string>();
+    }
+
+    public class OwnedRemoval
+    {
+        public List<string> Installations { get; set; } = new List<string>();
+        public List<string> Removed { get; set; } = new List<string>();
+        [JsonPropertyName("hand_edited")]
+        public List<string> HandEdited { get; set; } = new List<string>();
+        public List<string> Unowned { get; set; } = new List<string>();
     }
 
     public class RobloxInstallation
     {
+        public string Id { get; set; }
         public string Path { get; set; }
         public string Version { get; set; }
         public string Channel { get; set; }
         public string LastModified { get; set; }
+        public bool Primary { get; set; }
     }
 }
@@ -16,7 +16,7 @@ namespace Debeon.Services
 
         public ApiService()
         {
-            _httpClient = new HttpClient
+            _httpClient = new HttpClient(new SessionTokenHandler())
             {
                 Timeout = TimeSpan.FromSeconds(30)
             };
@@ -39,7 +39,7 @@ namespace Debeon.Services
         {
             try
             {
-                var response = await _httpClient.GetFromJsonAsync<ApiResponse<RobloxConfig>>($"{_baseUrl}/config/{profileName}");
+                var response = await _httpClient.GetFromJsonAsync<ApiResponse<RobloxConfig>>($"{_baseUrl}/config/{Uri.EscapeDataString(profileName)}");
                 return response?.Success == true ? response.Data : new RobloxConfig();
             }
             catch
@@ -54,7 +54,7 @@ namespace Debeon.Services
             {
                 var json = JsonConvert.SerializeObject(config);
                 var content = new StringContent(json, Encoding.UTF8, "application/json");
-                var response = await _httpClient.PostAsync($"{_baseUrl}/config/{profileName}", content);
+                var response = await _httpClient.PostAsync($"{_baseUrl}/config/{Uri.EscapeDataString(profileName)}", content);
                 return response.IsSuccessStatusCode;
             }
             catch
@@ -91,6 +91,20 @@ namespace Debeon.Services
             }
         }
 
+        public async Task<bool> DeleteProfileAsync(string profileName)
+        {
+            try
+            {
+                var response = await _httpClient.DeleteAsync($"{_baseUrl}/config/{Uri.EscapeDataString(profileName)}");
+                var result = await response.Content.ReadFromJsonAsync<ApiResponse<string>>();
+                return result?.Success == true;
+            }
+            catch
+            {
+                return false;
+            }
+        }
+
         public async Task<Dictionary<string, string>> GetFlagsAsync()
         {
             try
@@ -104,18 +118,46 @@ namespace Debeon.Services
             }
         }
 
-        public async Task<bool> SetFlagsAsync(Dictionary<string, string> flags)
+        public async Task<List<FlagPreset>> GetPresetsAsync()
+        {
+            try
+            {
+                var response = await _httpClient.GetFromJsonAsync<ApiResponse<List<FlagPreset>>>($"{_baseUrl}/presets");
+                return response?.Success == true ? response.Data : new List<FlagPreset>();
+            }
+            catch
+            {
+                return new List<FlagPreset>();
+            }
+        }
+
+        public async Task<OwnedRemoval> UninstallFlagsAsync()
+        {
+            try
+            {
+                var response = await _httpClient.PostAsync($"{_baseUrl}/flags/uninstall", null);
+                var result = await response.Content.ReadFromJsonAsync<ApiResponse<OwnedRemoval>>();
+                return result?.Success == true ? result.Data : null;
+            }
+            catch
+            {
+                return null;
+            }
+        }
+
+        public async Task<FlagsApplied> SetFlagsAsync(Dictionary<string, string> flags)
         {
             try
             {
                 var json = JsonConvert.SerializeObject(flags);
                 var content = new StringContent(json, Encoding.UTF8, "application/json");
                 var response = await _httpClient.PostAsync($"{_baseUrl}/flags", content);
-                return response.IsSuccessStatusCode;
+                var result = await response.Content.ReadFromJsonAsync<ApiResponse<FlagsApplied>>();
+                return result?.Success == true ? result.Data : null;
             }
             catch
             {
-                return false;
+                return null;
             }
         }
     }
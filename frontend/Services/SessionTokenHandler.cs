@@ -0,0 +1,36 @@
+using System;
+using System.IO;
+using System.Net.Http;
+using System.Net.Http.Headers;
+using System.Threading;
+using System.Threading.Tasks;
+
+namespace Debeon.Services
+{
+    public class SessionTokenHandler : DelegatingHandler
+    {
+        private readonly string _tokenPath;
+
+        public SessionTokenHandler()
+            : this(Path.Combine(Environment.GetFolderPath(Environment.SpecialFolder.ApplicationData), "Debeon", "session.token"))
+        {
+        }
+
+        public SessionTokenHandler(string tokenPath)
+        {
+            _tokenPath = tokenPath;
+            InnerHandler = new HttpClientHandler();
+        }
+
+        protected override Task<HttpResponseMessage> SendAsync(HttpRequestMessage request, CancellationToken cancellationToken)
+        {
+            if (File.Exists(_tokenPath))
+            {
+                var token = File.ReadAllText(_tokenPath).Trim();
+                request.Headers.Authorization = new AuthenticationHeaderValue("Bearer", token);
+            }
+
+            return base.SendAsync(request, cancellationToken);
+        }
+    }
+}
@@ -25,7 +25,7 @@ namespace Debeon.Views
 
             if (installations.Count > 0)
             {
-                var installation = installations[0];
+                var installation = installations.Find(i => i.Primary) ?? installations[0];
                 InstallationPathText.Text = installation.Path;
                 VersionText.Text = installation.Version;
                 ChannelText.Text = installation.Channel;
@@ -1,5 +1,6 @@
 using System.Collections.Generic;
 using System.Linq;
+using System.Threading.Tasks;
 using System.Windows;
 using System.Windows.Controls;
 using Debeon.Services;
@@ -41,49 +42,69 @@ namespace Debeon.Views
             }
         }
 
-        private void ClearAllFlags(object sender, RoutedEventArgs e)
+        private async void ClearAllFlags(object sender, RoutedEventArgs e)
         {
-            var result = MessageBox.Show("Are you sure you want to clear all custom flags?", "Confirm", MessageBoxButton.YesNo, MessageBoxImage.Question);
+            var result = MessageBox.Show("Are you sure you want to clear all custom flags? Flags Debeon wrote are removed, flags you edited by hand are kept, and a backup of the current Roblox settings is taken first.", "Confirm", MessageBoxButton.YesNo, MessageBoxImage.Question);
 
             if (result == MessageBoxResult.Yes)
             {
                 _config.CustomFlags.Clear();
                 RefreshFlagsList();
-                MessageBox.Show("All custom flags cleared.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+
+                var removal = await _apiService.UninstallFlagsAsync();
+                if (removal != null)
+                {
+                    var kept = removal.HandEdited.Count + removal.Unowned.Count;
+                    var message = kept > 0
+                        ? $"Removed {removal.Removed.Count} flag(s) set by Debeon. Kept {kept} flag(s) edited by hand or set by other tools."
+                        : $"Removed {removal.Removed.Count} flag(s) set by Debeon.";
+                    MessageBox.Show(message, "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+                }
+                else
+                {
+                    MessageBox.Show("Cleared the local list, but failed to clear flags in Roblox. Make sure the backend is running.", "Error", MessageBoxButton.OK, MessageBoxImage.Error);
+                }
             }
         }
 
-        private void ApplyUncapFpsPreset(object sender, RoutedEventArgs e)
+        private async void ApplyUncapFpsPreset(object sender, RoutedEventArgs e)
         {
-            _config.CustomFlags["DFIntTaskSchedulerTargetFps"] = "999";
-            RefreshFlagsList();
-            MessageBox.Show("Uncap FPS preset applied.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+            await ApplyPresetAsync("Uncap FPS");
         }
 
-        private void ApplyLowLatencyPreset(object sender, RoutedEventArgs e)
+        private async void ApplyLowLatencyPreset(object sender, RoutedEventArgs e)
         {
-            _config.CustomFlags["FFlagEnableLowLatencyMode"] = "true";
-            _config.CustomFlags["DFIntConnectionMTUSize"] = "1492";
-            RefreshFlagsList();
-            MessageBox.Show("Low Latency preset applied.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+            await ApplyPresetAsync("Low Latency");
         }
 
-        private void ApplyUltraGraphicsPreset(object sender, RoutedEventArgs e)
+        private async void ApplyUltraGraphicsPreset(object sender, RoutedEventArgs e)
         {
-            _config.CustomFlags["DFIntDebugFRMQualityLevelOverride"] = "21";
-            _config.CustomFlags["FIntRenderShadowIntensity"] = "100";
-            _config.CustomFlags["DFIntTextureQualityOverride"] = "3";
-            RefreshFlagsList();
-            MessageBox.Show("Ultra Graphics preset applied.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+            await ApplyPresetAsync("Ultra Graphics");
+        }
+
+        private async void ApplyPotatoModePreset(object sender, RoutedEventArgs e)
+        {
+            await ApplyPresetAsync("Potato Mode");
         }
 
-        private void ApplyPotatoModePreset(object sender, RoutedEventArgs e)
+        private async Task ApplyPresetAsync(string presetName)
         {
-            _config.CustomFlags["DFIntDebugFRMQualityLevelOverride"] = "1";
-            _config.CustomFlags["FFlagDisablePostFx"] = "true";
-            _config.CustomFlags["FIntRenderShadowIntensity"] = "0";
+            var presets = await _apiService.GetPresetsAsync();
+            var preset = presets.Find(p => p.Name == presetName);
+
+            if (preset == null)
+            {
+                MessageBox.Show("Unable to load presets. Make sure the backend server is running.", "Error", MessageBoxButton.OK, MessageBoxImage.Error);
+                return;
+            }
+
+            foreach (var flag in preset.Flags)
+            {
+                _config.CustomFlags[flag.Key] = flag.Value;
+            }
+
             RefreshFlagsList();
-            MessageBox.Show("Potato Mode preset applied.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
+            MessageBox.Show($"{preset.Name} preset applied.", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
         }
 
         private void AddCustomFlag(object sender, RoutedEventArgs e)
@@ -118,9 +139,14 @@ namespace Debeon.Views
 
         private async void SaveCustomFlags(object sender, RoutedEventArgs e)
         {
-            bool success = await _apiService.SetFlagsAsync(_config.CustomFlags);
+            var result = await _apiService.SetFlagsAsync(_config.CustomFlags);
 
-            if (success)
+            if (result != null && result.Warnings.Count > 0)
+            {
+                var warnings = string.Join("\n", result.Warnings.Select(w => w.Message));
+                MessageBox.Show($"Custom flags applied with warnings:\n\n{warnings}", "Warning", MessageBoxButton.OK, MessageBoxImage.Warning);
+            }
+            else if (result != null)
             {
                 MessageBox.Show("Custom flags applied successfully!", "Success", MessageBoxButton.OK, MessageBoxImage.Information);
             }
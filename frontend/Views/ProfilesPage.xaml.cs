@@ -106,7 +106,13 @@ namespace Debeon.Views
 
             if (result == MessageBoxResult.Yes)
             {
-                MessageBox.Show($"Profile '{profileName}' would be deleted here.", "Info", MessageBoxButton.OK, MessageBoxImage.Information);
+                bool success = await _apiService.DeleteProfileAsync(profileName);
+
+                if (!success)
+                {
+                    MessageBox.Show($"Failed to delete profile '{profileName}'.", "Error", MessageBoxButton.OK, MessageBoxImage.Error);
+                }
+
                 await LoadProfilesList();
             }
         }